@@ -0,0 +1,198 @@
+/// Tunes how lenient extraction is with regards to the shape of the source
+///
+/// The default configuration is the one used by [`extract`](crate::extract). Use
+/// [`extract_with`](crate::extract_with) or [`DeserializerFromSerializable::with_config`](crate::DeserializerFromSerializable::with_config)
+/// to extract with a different one.
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// enum Source {
+/// 	A(Inner),
+/// }
+/// #[derive(serde_derive::Serialize)]
+/// struct Inner {
+/// 	b: u32,
+/// }
+/// #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+/// struct Extract {
+/// 	b: u32,
+/// }
+/// let config = serde_extract::Config::new().look_through_newtype_variants(true);
+/// assert_eq!(
+/// 	Extract { b: 3 },
+/// 	serde_extract::extract_with(&Source::A(Inner { b: 3 }), config).unwrap(),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Config {
+	pub(crate) look_through_newtype_variants: bool,
+	pub(crate) variant_fields: VariantFields,
+	pub(crate) none_as_missing: bool,
+	pub(crate) present_none_as_some: bool,
+	pub(crate) variant_matching: VariantMatching,
+	pub(crate) tuple_fields: TupleFields,
+}
+
+impl Config {
+	pub const fn new() -> Self {
+		Self {
+			look_through_newtype_variants: false,
+			variant_fields: VariantFields::Rejected,
+			none_as_missing: false,
+			present_none_as_some: false,
+			variant_matching: VariantMatching::Name,
+			tuple_fields: TupleFields::Rejected,
+		}
+	}
+
+	/// When looking up fields, consider that `Enum::Variant(Inner)` has the fields of `Inner`
+	///
+	/// Newtype structs and `Some` are always looked through, but newtype variants are not by default because this
+	/// discards the variant name.
+	pub const fn look_through_newtype_variants(mut self, enabled: bool) -> Self {
+		self.look_through_newtype_variants = enabled;
+		self
+	}
+
+	/// When looking up fields, whether `Enum::Variant { a, b }` and `Enum::Variant(a, b)` should be considered as
+	/// having fields `a` and `b` (resp. `0` and `1`)
+	pub const fn variant_fields(mut self, variant_fields: VariantFields) -> Self {
+		self.variant_fields = variant_fields;
+		self
+	}
+
+	/// Consider that source fields that serialize as `none` or `unit` are absent
+	///
+	/// This enables target fields that aren't `Option`s to get their `#[serde(default)]` value when the corresponding
+	/// source field is `None`.
+	pub const fn none_as_missing(mut self, enabled: bool) -> Self {
+		self.none_as_missing = enabled;
+		self
+	}
+
+	/// Hand source values that serialize as `none` to `Option` targets as a `Some` of an inner `None`
+	///
+	/// This lets `Option<Option<T>>` targets tell a `None` of the source (`Some(None)`) from an absent field (`None`),
+	/// as is usual for "explicitly null" in PATCH APIs. Without it, both extract as `None`.
+	///
+	/// Serde doesn't tell the deserializer whether an `Option` holds another one, so with this enabled, extracting a
+	/// `None` into an `Option<T>` (or any other type that deserializes through `deserialize_option`) where `T` isn't
+	/// itself optional is an error.
+	pub const fn present_none_as_some(mut self, enabled: bool) -> Self {
+		self.present_none_as_some = enabled;
+		self
+	}
+
+	/// How source enum variants are matched with target enum variants
+	pub const fn variant_matching(mut self, variant_matching: VariantMatching) -> Self {
+		self.variant_matching = variant_matching;
+		self
+	}
+
+	/// Whether and how fields of the target struct can be extracted from tuples and tuple structs
+	///
+	/// This also affects how fields are extracted from tuple variants (see [`Config::variant_fields`]), which are
+	/// otherwise matched by numeric name.
+	///
+	/// The reverse doesn't need to be enabled: targets that are deserialized as tuples or tuple structs (e.g.
+	/// `(u32, String)`) are always filled from the fields of a struct source or the values of a map source, in order.
+	/// Use [`extract_fields`](crate::extract_fields) to pick the fields by name instead.
+	pub const fn tuple_fields(mut self, tuple_fields: TupleFields) -> Self {
+		self.tuple_fields = tuple_fields;
+		self
+	}
+}
+
+/// Whether fields can be extracted from struct and tuple variants
+///
+/// See [`Config::variant_fields`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VariantFields {
+	/// Attempting to extract fields from a struct or tuple variant is an error
+	#[default]
+	Rejected,
+	/// Fields can be extracted from any struct or tuple variant
+	AnyVariant,
+	/// Fields can only be extracted from struct or tuple variants that have the same name as the target struct
+	///
+	/// Attempting to extract fields from a variant with another name is an error.
+	MatchingName,
+}
+
+/// How source enum variants are matched with target enum variants
+///
+/// See [`Config::variant_matching`]
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// enum Source {
+/// 	A,
+/// 	B(u32),
+/// }
+/// #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+/// enum Target {
+/// 	First,
+/// 	Second(u32),
+/// }
+/// let config = serde_extract::Config::new().variant_matching(serde_extract::VariantMatching::IndexIfUnknownName);
+/// assert_eq!(
+/// 	Target::Second(3),
+/// 	serde_extract::extract_with(&Source::B(3), config).unwrap(),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VariantMatching {
+	/// Variants are matched by name
+	#[default]
+	Name,
+	/// Variants are matched by name if the target enum has a variant with that name, and by index otherwise
+	IndexIfUnknownName,
+	/// Variants are matched by index
+	///
+	/// If the target enum has aliases, a newtype variant whose index is past the target's variants is only caught
+	/// by its `#[serde(other)]` variant if the index is also past the number of aliases and names together:
+	/// otherwise it can't be told apart from a unit variant of the target and is an error.
+	Index,
+}
+
+impl VariantMatching {
+	/// Whether the variant should be identified by its index
+	///
+	/// `target_variants` is only known if the enum was hinted with `deserialize_enum`.
+	pub(crate) fn by_index(self, variant: &str, target_variants: Option<&[&str]>) -> bool {
+		match self {
+			VariantMatching::Name => false,
+			VariantMatching::IndexIfUnknownName => target_variants.is_some_and(|variants| !variants.contains(&variant)),
+			VariantMatching::Index => true,
+		}
+	}
+}
+
+/// Whether and how fields of the target struct can be extracted from tuples and tuple structs
+///
+/// See [`Config::tuple_fields`]
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Source(i32, i32);
+/// #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+/// struct Point {
+/// 	x: i32,
+/// 	y: i32,
+/// }
+/// let config = serde_extract::Config::new().tuple_fields(serde_extract::TupleFields::ByPosition);
+/// assert_eq!(
+/// 	Point { x: 1, y: 2 },
+/// 	serde_extract::extract_with(&Source(1, 2), config).unwrap(),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TupleFields {
+	/// Attempting to extract fields from a tuple or tuple struct is an error
+	#[default]
+	Rejected,
+	/// Fields named `"0"`, `"1"`... are extracted from the tuple element at that position
+	ByNumericName,
+	/// The `i`-th field of the target struct is extracted from the tuple element at position `i`
+	ByPosition,
+}
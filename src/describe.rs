@@ -0,0 +1,533 @@
+use {
+	super::*,
+	core::fmt,
+	walk::{Leaf, Node, Walk},
+};
+
+/// The structure of a `Serialize` value, see [`describe`](crate::describe)
+///
+/// Displaying it walks the value without allocating, e.g.
+/// `Request { page_size: Some(u64), ids: [u32, u32], kind: Kind::Search }`. If the value fails to serialize, the
+/// description stops there with `<error>`.
+#[cfg_attr(
+	feature = "alloc",
+	doc = "With the `alloc` feature, [`Description::shape`] captures the structure as an owned [`Shape`] tree instead."
+)]
+#[cfg_attr(
+	not(feature = "alloc"),
+	doc = "With the `alloc` feature, `Description::shape` captures the structure as an owned `Shape` tree instead."
+)]
+pub struct Description<'s, S: ?Sized> {
+	serializable: &'s S,
+}
+
+impl<'s, S: Serialize + ?Sized> Description<'s, S> {
+	pub(super) fn new(serializable: &'s S) -> Self {
+		Self { serializable }
+	}
+
+	/// Capture the structure of the value as an owned tree
+	#[cfg(feature = "alloc")]
+	pub fn shape(&self) -> Result<Shape, Error> {
+		let mut builder = ShapeBuilder::default();
+		walk(self.serializable, &mut builder)?;
+		builder.done.ok_or(Error::new(
+			ErrorCode::Internal,
+			"Walk ended before the value was complete",
+		))
+	}
+}
+
+impl<S: Serialize + ?Sized> fmt::Display for Description<'_, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut formatter = ShapeFormatter::new(f);
+		match walk(self.serializable, &mut formatter) {
+			Ok(()) => Ok(()),
+			Err(_) if formatter.write_failed => Err(fmt::Error),
+			// `Display` can't report why the value failed to serialize, so the description just stops there
+			Err(_) => formatter.out.write_str("<error>"),
+		}
+	}
+}
+
+impl<S: Serialize + ?Sized> fmt::Debug for Description<'_, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+/// Writes the structure of the value in a Rust-like syntax
+///
+/// Leaves are written as their type (e.g. `u32`), except within map keys where their value is written, because that's
+/// usually what tells the entries apart.
+struct ShapeFormatter<W> {
+	out: W,
+	/// Whether nothing has been written yet in the current struct, to know whether to separate fields
+	first: bool,
+	in_key: bool,
+	/// Whether the error that stopped the walk comes from `out` rather than from the value
+	write_failed: bool,
+}
+
+impl<W: fmt::Write> ShapeFormatter<W> {
+	fn new(out: W) -> Self {
+		Self {
+			out,
+			first: true,
+			in_key: false,
+			write_failed: false,
+		}
+	}
+
+	fn write(&mut self, args: fmt::Arguments) -> Result<(), Error> {
+		self.out.write_fmt(args).map_err(|_| {
+			self.write_failed = true;
+			Error::new(ErrorCode::Custom, "Failed to write the description")
+		})
+	}
+}
+
+impl<W: fmt::Write> Walk for ShapeFormatter<W> {
+	fn enter(&mut self, node: Node) -> Result<(), Error> {
+		self.first = true;
+		match node {
+			Node::Struct { name, .. } => self.write(format_args!("{} {{", name)),
+			Node::StructVariant { name, variant, .. } => self.write(format_args!("{}::{} {{", name, variant)),
+			Node::Seq { .. } => self.write(format_args!("[")),
+			Node::Tuple { .. } => self.write(format_args!("(")),
+			Node::TupleStruct { name, .. } | Node::NewtypeStruct { name } => self.write(format_args!("{}(", name)),
+			Node::TupleVariant { name, variant, .. } | Node::NewtypeVariant { name, variant } => {
+				self.write(format_args!("{}::{}(", name, variant))
+			}
+			Node::Map { .. } => self.write(format_args!("{{")),
+			Node::Some => self.write(format_args!("Some(")),
+		}
+	}
+
+	fn field(&mut self, name: &'static str) -> Result<(), Error> {
+		let separator = if self.first { " " } else { ", " };
+		self.first = false;
+		self.write(format_args!("{}{}: ", separator, name))
+	}
+
+	fn element(&mut self, idx: usize) -> Result<(), Error> {
+		match idx {
+			0 => Ok(()),
+			_ => self.write(format_args!(", ")),
+		}
+	}
+
+	fn key(&mut self, idx: usize) -> Result<(), Error> {
+		self.in_key = true;
+		self.element(idx)
+	}
+
+	fn value(&mut self, _: usize) -> Result<(), Error> {
+		self.in_key = false;
+		self.write(format_args!(": "))
+	}
+
+	fn leaf(&mut self, leaf: Leaf<'_>) -> Result<(), Error> {
+		match (leaf, self.in_key) {
+			(Leaf::Bool(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::I8(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::I16(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::I32(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::I64(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::I128(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::U8(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::U16(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::U32(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::U64(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::U128(v), true) => self.write(format_args!("{}", v)),
+			(Leaf::Char(v), true) => self.write(format_args!("{:?}", v)),
+			(Leaf::Str(v), true) => self.write(format_args!("{:?}", v)),
+			(Leaf::UnitStruct(name), _) => self.write(format_args!("{}", name)),
+			(Leaf::UnitVariant { name, variant }, _) => self.write(format_args!("{}::{}", name, variant)),
+			(leaf, _) => self.write(format_args!("{}", LeafKind::of(leaf))),
+		}
+	}
+
+	fn exit(&mut self, node: Node) -> Result<(), Error> {
+		let result = match node {
+			Node::Struct { .. } | Node::StructVariant { .. } if self.first => self.write(format_args!("}}")),
+			Node::Struct { .. } | Node::StructVariant { .. } => self.write(format_args!(" }}")),
+			Node::Seq { .. } => self.write(format_args!("]")),
+			Node::Map { .. } => self.write(format_args!("}}")),
+			Node::Tuple { .. }
+			| Node::TupleStruct { .. }
+			| Node::TupleVariant { .. }
+			| Node::Some
+			| Node::NewtypeStruct { .. }
+			| Node::NewtypeVariant { .. } => self.write(format_args!(")")),
+		};
+		self.first = false;
+		result
+	}
+}
+
+/// The kind of a leaf, as written by [`ShapeFormatter`]
+struct LeafKind(&'static str);
+
+impl LeafKind {
+	fn of(leaf: Leaf<'_>) -> Self {
+		LeafKind(match leaf {
+			Leaf::Bool(_) => "bool",
+			Leaf::I8(_) => "i8",
+			Leaf::I16(_) => "i16",
+			Leaf::I32(_) => "i32",
+			Leaf::I64(_) => "i64",
+			Leaf::I128(_) => "i128",
+			Leaf::U8(_) => "u8",
+			Leaf::U16(_) => "u16",
+			Leaf::U32(_) => "u32",
+			Leaf::U64(_) => "u64",
+			Leaf::U128(_) => "u128",
+			Leaf::F32(_) => "f32",
+			Leaf::F64(_) => "f64",
+			Leaf::Char(_) => "char",
+			Leaf::Str(_) => "str",
+			Leaf::Bytes(_) => "bytes",
+			Leaf::None => "None",
+			Leaf::Unit => "()",
+			Leaf::UnitStruct(name) | Leaf::UnitVariant { name, .. } => name,
+		})
+	}
+}
+
+impl fmt::Display for LeafKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.0)
+	}
+}
+
+/// The structure of a `Serialize` value, following the serde data model
+///
+/// Its [`Display`](fmt::Display) implementation writes the same as that of [`Description`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Shape {
+	Bool,
+	I8,
+	I16,
+	I32,
+	I64,
+	I128,
+	U8,
+	U16,
+	U32,
+	U64,
+	U128,
+	F32,
+	F64,
+	Char,
+	Str,
+	Bytes,
+	None,
+	Unit,
+	UnitStruct {
+		name: &'static str,
+	},
+	UnitVariant {
+		name: &'static str,
+		variant: &'static str,
+	},
+	Some(Box<Shape>),
+	NewtypeStruct {
+		name: &'static str,
+		value: Box<Shape>,
+	},
+	NewtypeVariant {
+		name: &'static str,
+		variant: &'static str,
+		value: Box<Shape>,
+	},
+	Seq(Vec<Shape>),
+	Tuple(Vec<Shape>),
+	TupleStruct {
+		name: &'static str,
+		fields: Vec<Shape>,
+	},
+	TupleVariant {
+		name: &'static str,
+		variant: &'static str,
+		fields: Vec<Shape>,
+	},
+	/// Keys are written as they are displayed, e.g. `"a"` for the string `a`
+	Map(Vec<(String, Shape)>),
+	Struct {
+		name: &'static str,
+		fields: Vec<(&'static str, Shape)>,
+	},
+	StructVariant {
+		name: &'static str,
+		variant: &'static str,
+		fields: Vec<(&'static str, Shape)>,
+	},
+}
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Shape {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fn list(f: &mut fmt::Formatter, shapes: &[Shape]) -> fmt::Result {
+			for (idx, shape) in shapes.iter().enumerate() {
+				if idx > 0 {
+					f.write_str(", ")?;
+				}
+				write!(f, "{}", shape)?;
+			}
+			Ok(())
+		}
+		fn fields(f: &mut fmt::Formatter, fields: &[(&'static str, Shape)]) -> fmt::Result {
+			for (idx, (name, shape)) in fields.iter().enumerate() {
+				f.write_str(if idx > 0 { ", " } else { " " })?;
+				write!(f, "{}: {}", name, shape)?;
+			}
+			f.write_str(if fields.is_empty() { "}" } else { " }" })
+		}
+		match self {
+			Shape::UnitStruct { name } => f.write_str(name),
+			Shape::UnitVariant { name, variant } => write!(f, "{}::{}", name, variant),
+			Shape::Some(value) => write!(f, "Some({})", value),
+			Shape::NewtypeStruct { name, value } => write!(f, "{}({})", name, value),
+			Shape::NewtypeVariant { name, variant, value } => write!(f, "{}::{}({})", name, variant, value),
+			Shape::Seq(elements) => {
+				f.write_str("[")?;
+				list(f, elements)?;
+				f.write_str("]")
+			}
+			Shape::Tuple(elements) => {
+				f.write_str("(")?;
+				list(f, elements)?;
+				f.write_str(")")
+			}
+			Shape::TupleStruct { name, fields } => {
+				write!(f, "{}(", name)?;
+				list(f, fields)?;
+				f.write_str(")")
+			}
+			Shape::TupleVariant { name, variant, fields } => {
+				write!(f, "{}::{}(", name, variant)?;
+				list(f, fields)?;
+				f.write_str(")")
+			}
+			Shape::Map(entries) => {
+				f.write_str("{")?;
+				for (idx, (key, value)) in entries.iter().enumerate() {
+					if idx > 0 {
+						f.write_str(", ")?;
+					}
+					write!(f, "{}: {}", key, value)?;
+				}
+				f.write_str("}")
+			}
+			Shape::Struct { name, fields: f_ } => {
+				write!(f, "{} {{", name)?;
+				fields(f, f_)
+			}
+			Shape::StructVariant {
+				name,
+				variant,
+				fields: f_,
+			} => {
+				write!(f, "{}::{} {{", name, variant)?;
+				fields(f, f_)
+			}
+			leaf => f.write_str(match leaf {
+				Shape::Bool => "bool",
+				Shape::I8 => "i8",
+				Shape::I16 => "i16",
+				Shape::I32 => "i32",
+				Shape::I64 => "i64",
+				Shape::I128 => "i128",
+				Shape::U8 => "u8",
+				Shape::U16 => "u16",
+				Shape::U32 => "u32",
+				Shape::U64 => "u64",
+				Shape::U128 => "u128",
+				Shape::F32 => "f32",
+				Shape::F64 => "f64",
+				Shape::Char => "char",
+				Shape::Str => "str",
+				Shape::Bytes => "bytes",
+				Shape::None => "None",
+				_ => "()",
+			}),
+		}
+	}
+}
+
+/// Builds a [`Shape`] from the walk of a value
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+struct ShapeBuilder {
+	/// The nodes we're in, along with what has been walked of them so far
+	stack: Vec<Frame>,
+	/// The key that is being written, and how deep into it we are
+	key: Option<(ShapeFormatter<String>, usize)>,
+	done: Option<Shape>,
+}
+
+#[cfg(feature = "alloc")]
+struct Frame {
+	node: Node,
+	shapes: Vec<Shape>,
+	names: Vec<&'static str>,
+	keys: Vec<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl ShapeBuilder {
+	fn push(&mut self, shape: Shape) {
+		match self.stack.last_mut() {
+			Some(frame) => frame.shapes.push(shape),
+			None => self.done = Some(shape),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Walk for ShapeBuilder {
+	fn enter(&mut self, node: Node) -> Result<(), Error> {
+		if let Some((key, depth)) = &mut self.key {
+			*depth += 1;
+			return key.enter(node);
+		}
+		self.stack.push(Frame {
+			node,
+			shapes: Vec::new(),
+			names: Vec::new(),
+			keys: Vec::new(),
+		});
+		Ok(())
+	}
+
+	fn field(&mut self, name: &'static str) -> Result<(), Error> {
+		if let Some((key, _)) = &mut self.key {
+			return key.field(name);
+		}
+		if let Some(frame) = self.stack.last_mut() {
+			frame.names.push(name);
+		}
+		Ok(())
+	}
+
+	fn element(&mut self, idx: usize) -> Result<(), Error> {
+		match &mut self.key {
+			Some((key, _)) => key.element(idx),
+			None => Ok(()),
+		}
+	}
+
+	fn key(&mut self, idx: usize) -> Result<(), Error> {
+		match &mut self.key {
+			Some((key, _)) => key.key(idx),
+			None => {
+				let mut key = ShapeFormatter::new(String::new());
+				key.in_key = true;
+				self.key = Some((key, 0));
+				Ok(())
+			}
+		}
+	}
+
+	fn value(&mut self, idx: usize) -> Result<(), Error> {
+		match self.key.take() {
+			Some((mut key, depth)) if depth > 0 => {
+				let result = key.value(idx);
+				self.key = Some((key, depth));
+				result
+			}
+			Some((key, _)) => {
+				if let Some(frame) = self.stack.last_mut() {
+					frame.keys.push(key.out);
+				}
+				Ok(())
+			}
+			None => Ok(()),
+		}
+	}
+
+	fn leaf(&mut self, leaf: Leaf<'_>) -> Result<(), Error> {
+		if let Some((key, _)) = &mut self.key {
+			return key.leaf(leaf);
+		}
+		self.push(match leaf {
+			Leaf::Bool(_) => Shape::Bool,
+			Leaf::I8(_) => Shape::I8,
+			Leaf::I16(_) => Shape::I16,
+			Leaf::I32(_) => Shape::I32,
+			Leaf::I64(_) => Shape::I64,
+			Leaf::I128(_) => Shape::I128,
+			Leaf::U8(_) => Shape::U8,
+			Leaf::U16(_) => Shape::U16,
+			Leaf::U32(_) => Shape::U32,
+			Leaf::U64(_) => Shape::U64,
+			Leaf::U128(_) => Shape::U128,
+			Leaf::F32(_) => Shape::F32,
+			Leaf::F64(_) => Shape::F64,
+			Leaf::Char(_) => Shape::Char,
+			Leaf::Str(_) => Shape::Str,
+			Leaf::Bytes(_) => Shape::Bytes,
+			Leaf::None => Shape::None,
+			Leaf::Unit => Shape::Unit,
+			Leaf::UnitStruct(name) => Shape::UnitStruct { name },
+			Leaf::UnitVariant { name, variant } => Shape::UnitVariant { name, variant },
+		});
+		Ok(())
+	}
+
+	fn exit(&mut self, node: Node) -> Result<(), Error> {
+		if let Some((key, depth)) = &mut self.key {
+			*depth -= 1;
+			return key.exit(node);
+		}
+		let Frame {
+			node,
+			mut shapes,
+			names,
+			keys,
+		} = self
+			.stack
+			.pop()
+			.ok_or(Error::new(ErrorCode::Internal, "Exited more nodes than were entered"))?;
+		let mut value = || {
+			shapes
+				.pop()
+				.map(Box::new)
+				.ok_or(Error::new(ErrorCode::Internal, "Newtype without a value"))
+		};
+		let shape = match node {
+			Node::Some => Shape::Some(value()?),
+			Node::NewtypeStruct { name } => Shape::NewtypeStruct { name, value: value()? },
+			Node::NewtypeVariant { name, variant } => Shape::NewtypeVariant {
+				name,
+				variant,
+				value: value()?,
+			},
+			Node::Seq { .. } => Shape::Seq(shapes),
+			Node::Tuple { .. } => Shape::Tuple(shapes),
+			Node::TupleStruct { name, .. } => Shape::TupleStruct { name, fields: shapes },
+			Node::TupleVariant { name, variant, .. } => Shape::TupleVariant {
+				name,
+				variant,
+				fields: shapes,
+			},
+			Node::Map { .. } => Shape::Map(keys.into_iter().zip(shapes).collect()),
+			Node::Struct { name, .. } => Shape::Struct {
+				name,
+				fields: names.into_iter().zip(shapes).collect(),
+			},
+			Node::StructVariant { name, variant, .. } => Shape::StructVariant {
+				name,
+				variant,
+				fields: names.into_iter().zip(shapes).collect(),
+			},
+		};
+		self.push(shape);
+		Ok(())
+	}
+}
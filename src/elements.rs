@@ -0,0 +1,65 @@
+use super::*;
+
+/// Iterator over the elements of a sequence source, see [`elements`](crate::elements) and
+/// [`elements_at`](crate::elements_at)
+///
+/// The [`Serializer`] is re-driven up to the next element on every call to [`Iterator::next`], which is O(n²) where n is
+/// the number of elements, but nothing is extracted past the last element that was asked for.
+pub struct Elements<'s, S: ?Sized, T> {
+	serializable: &'s S,
+	/// Field of the source that holds the sequence, if not the source itself
+	field: Option<&'s str>,
+	config: Config,
+	/// Index of the next element to extract
+	idx: usize,
+	finished: bool,
+	_spooky: PhantomData<fn() -> T>,
+}
+
+impl<'s, S: Serialize + ?Sized, T> Elements<'s, S, T> {
+	pub(super) fn new(serializable: &'s S, field: Option<&'s str>, config: Config) -> Self {
+		Self {
+			serializable,
+			field,
+			config,
+			idx: 0,
+			finished: false,
+			_spooky: PhantomData,
+		}
+	}
+
+	/// Sinks the sequence, erroring if it's supposed to be in a field that is absent from the source
+	fn lookup<K: ValueSink<'s>>(&self, sink: K) -> Result<K::Value, Error> {
+		match self.field {
+			None => sink.sink(self.serializable, self.config),
+			Some(field) => self
+				.serializable
+				.serialize(map_access_from_serizable::ExtractFieldByNameSerializer::new(
+					field, sink, self.config,
+				))?
+				.into_option()?
+				.ok_or_else(|| Error::formatted(ErrorCode::MissingField, format_args!("missing field `{}`", field))),
+		}
+	}
+}
+
+impl<S: Serialize + ?Sized, T: DeserializeOwned> Iterator for Elements<'_, S, T> {
+	type Item = Result<T, Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.finished {
+			return None;
+		}
+		let element = self
+			.lookup(seq_access_from_serializable::ElementAt {
+				idx: self.idx,
+				_spooky: PhantomData,
+			})
+			.transpose();
+		match element {
+			Some(Ok(_)) => self.idx += 1,
+			Some(Err(_)) | None => self.finished = true,
+		}
+		element
+	}
+}
@@ -0,0 +1,197 @@
+use super::*;
+
+/// Iterator over the entries of a struct or map source, see [`entries`](crate::entries)
+///
+/// The [`Serializer`] is re-driven up to the next entry on every call to [`Iterator::next`], which is O(n²) where n is
+/// the number of entries, but nothing is extracted past the last entry that was asked for.
+pub struct Entries<'s, S: ?Sized, K, V> {
+	serializable: &'s S,
+	config: Config,
+	/// Index of the next entry to extract
+	start_idx: usize,
+	finished: bool,
+	_spooky: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'s, S: Serialize + ?Sized, K, V> Entries<'s, S, K, V> {
+	pub(super) fn new(serializable: &'s S, config: Config) -> Self {
+		Self {
+			serializable,
+			config,
+			start_idx: 0,
+			finished: false,
+			_spooky: PhantomData,
+		}
+	}
+}
+
+impl<S: Serialize + ?Sized, K: DeserializeOwned, V: DeserializeOwned> Iterator for Entries<'_, S, K, V> {
+	type Item = Result<(K, V), Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.finished {
+			return None;
+		}
+		let entry = self
+			.serializable
+			.serialize(EntriesSerializer {
+				start_idx: self.start_idx,
+				config: self.config,
+				_spooky: PhantomData,
+			})
+			.unwrap_or_else(|e| Some(Err(e)));
+		match entry {
+			Some(Ok(_)) => self.start_idx += 1,
+			Some(Err(_)) | None => self.finished = true,
+		}
+		entry
+	}
+}
+
+/// The entry at `start_idx`, if the source has that many
+type Found<K, V> = Option<Result<(K, V), Error>>;
+
+struct EntriesSerializer<K, V> {
+	/// Entries before that one have already been extracted
+	start_idx: usize,
+	config: Config,
+	_spooky: PhantomData<fn() -> (K, V)>,
+}
+
+#[allow(deprecated)] // `serializer_unsupported!` expands to the deprecated `serde_if_integer128!`
+impl<K: DeserializeOwned, V: DeserializeOwned> Serializer for EntriesSerializer<K, V> {
+	type Ok = Found<K, V>;
+	type Error = Error;
+
+	type SerializeMap = EntriesSerializeStructOrMap<K, V>;
+	fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		Ok(EntriesSerializeStructOrMap {
+			start_idx: self.start_idx,
+			current_idx: 0,
+			pending_key: None,
+			found: None,
+			config: self.config,
+		})
+	}
+
+	type SerializeStruct = EntriesSerializeStructOrMap<K, V>;
+	fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
+		self.serialize_map(None)
+	}
+
+	fn serialize_newtype_struct<T: ?Sized>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	serializer_unsupported! {
+		err = (Error::new(ErrorCode::Unsupported, "Can only iterate over the entries of maps and structs"));
+		bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str bytes unit unit_struct unit_variant newtype_variant
+		seq tuple tuple_struct tuple_variant struct_variant i128 u128
+	}
+
+	#[cfg(not(feature = "alloc"))]
+	fn collect_str<T: ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: core::fmt::Display,
+	{
+		Err(Error::new(
+			ErrorCode::Unsupported,
+			"Can only iterate over the entries of maps and structs",
+		))
+	}
+}
+
+struct EntriesSerializeStructOrMap<K, V> {
+	start_idx: usize,
+	current_idx: usize,
+	/// Key of the current entry, if it is to be extracted, until we get its value
+	pending_key: Option<K>,
+	found: Found<K, V>,
+	config: Config,
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> EntriesSerializeStructOrMap<K, V> {
+	fn wants_current(&self) -> bool {
+		self.current_idx >= self.start_idx && self.found.is_none()
+	}
+
+	fn value<T: Serialize + ?Sized>(&self, value: &T) -> Result<V, Error> {
+		V::deserialize(DeserializerFromSerializable {
+			serializable: value,
+			config: self.config,
+		})
+	}
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> SerializeMap for EntriesSerializeStructOrMap<K, V> {
+	type Ok = Found<K, V>;
+	type Error = Error;
+
+	fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		if self.wants_current() {
+			match K::deserialize(DeserializerFromSerializable {
+				serializable: key,
+				config: self.config,
+			}) {
+				Ok(key) => self.pending_key = Some(key),
+				Err(e) => self.found = Some(Err(e)),
+			}
+		}
+		Ok(())
+	}
+
+	fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		if let Some(key) = self.pending_key.take() {
+			let entry = self.value(value).map(|value| (key, value));
+			self.found = Some(entry);
+		}
+		self.current_idx += 1;
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.found)
+	}
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> SerializeStruct for EntriesSerializeStructOrMap<K, V> {
+	type Ok = Found<K, V>;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		if self.wants_current() {
+			let entry =
+				K::deserialize(value::BorrowedStrDeserializer::new(key)).and_then(|key| Ok((key, self.value(value)?)));
+			self.found = Some(entry);
+		}
+		self.current_idx += 1;
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.found)
+	}
+}
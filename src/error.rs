@@ -0,0 +1,216 @@
+use core::fmt;
+
+/// The error type of extraction
+///
+/// It always carries an [`ErrorCode`] and, when relevant, the name of the offending field, so that errors remain
+/// meaningful without the `alloc` feature. Messages built from formatted text (e.g. by `Deserialize` implementations)
+/// are only kept with the `alloc` feature.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error {
+	code: ErrorCode,
+	field: Option<&'static str>,
+	message: Message,
+}
+
+/// What kind of [`Error`] happened
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+	/// Raised by a `Serialize` or `Deserialize` implementation through `custom`
+	Custom,
+	InvalidType,
+	InvalidValue,
+	InvalidLength,
+	UnknownVariant,
+	UnknownField,
+	MissingField,
+	DuplicateField,
+	/// A path given as a string is malformed
+	InvalidPath,
+	/// The shape of the source isn't supported, or doesn't fit what the target asks for
+	Unsupported,
+	/// The `Serialize` implementation of the source misbehaved, or there is a bug in this crate
+	Internal,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Message {
+	None,
+	Static(&'static str),
+	#[cfg(feature = "alloc")]
+	Owned(alloc::boxed::Box<str>),
+}
+
+impl Error {
+	pub(crate) const fn new(code: ErrorCode, message: &'static str) -> Self {
+		Self {
+			code,
+			field: None,
+			message: Message::Static(message),
+		}
+	}
+
+	/// The message is dropped without the `alloc` feature
+	pub(crate) fn formatted(code: ErrorCode, message: fmt::Arguments) -> Self {
+		Self {
+			code,
+			field: None,
+			#[cfg(feature = "alloc")]
+			message: Message::Owned(alloc::string::ToString::to_string(&message).into_boxed_str()),
+			#[cfg(not(feature = "alloc"))]
+			message: match message.as_str() {
+				Some(message) => Message::Static(message),
+				None => Message::None,
+			},
+		}
+	}
+
+	const fn with_field(code: ErrorCode, field: &'static str) -> Self {
+		Self {
+			code,
+			field: Some(field),
+			message: Message::None,
+		}
+	}
+
+	/// Records that the error happened while extracting `field`, unless it already happened in a nested field
+	pub(crate) fn at_field(mut self, field: &'static str) -> Self {
+		self.field.get_or_insert(field);
+		self
+	}
+
+	pub fn code(&self) -> ErrorCode {
+		self.code
+	}
+
+	/// The field that is missing, duplicated..., or the field that was being extracted when the error happened
+	pub fn field(&self) -> Option<&'static str> {
+		self.field
+	}
+}
+
+impl ErrorCode {
+	fn description(self) -> &'static str {
+		match self {
+			ErrorCode::Custom => "custom error",
+			ErrorCode::InvalidType => "invalid type",
+			ErrorCode::InvalidValue => "invalid value",
+			ErrorCode::InvalidLength => "invalid length",
+			ErrorCode::UnknownVariant => "unknown variant",
+			ErrorCode::UnknownField => "unknown field",
+			ErrorCode::MissingField => "missing field",
+			ErrorCode::DuplicateField => "duplicate field",
+			ErrorCode::InvalidPath => "invalid path",
+			ErrorCode::Unsupported => "unsupported",
+			ErrorCode::Internal => "internal error",
+		}
+	}
+}
+
+impl fmt::Display for ErrorCode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.description())
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match (&self.message, self.code, self.field) {
+			(Message::Static(message), _, _) => f.write_str(message),
+			#[cfg(feature = "alloc")]
+			(Message::Owned(message), _, _) => f.write_str(message),
+			(Message::None, ErrorCode::MissingField | ErrorCode::DuplicateField, Some(field)) => {
+				write!(f, "{} `{}`", self.code, field)
+			}
+			(Message::None, _, Some(field)) => write!(f, "{} at field `{}`", self.code, field),
+			(Message::None, _, None) => write!(f, "{}", self.code),
+		}
+	}
+}
+
+impl serde::de::StdError for Error {}
+
+impl serde::de::Error for Error {
+	fn custom<T: fmt::Display>(msg: T) -> Self {
+		Self::formatted(ErrorCode::Custom, format_args!("{}", msg))
+	}
+
+	fn invalid_type(unexp: serde::de::Unexpected, exp: &dyn serde::de::Expected) -> Self {
+		Self::formatted(
+			ErrorCode::InvalidType,
+			format_args!("invalid type: {}, expected {}", unexp, exp),
+		)
+	}
+
+	fn invalid_value(unexp: serde::de::Unexpected, exp: &dyn serde::de::Expected) -> Self {
+		Self::formatted(
+			ErrorCode::InvalidValue,
+			format_args!("invalid value: {}, expected {}", unexp, exp),
+		)
+	}
+
+	fn invalid_length(len: usize, exp: &dyn serde::de::Expected) -> Self {
+		Self::formatted(
+			ErrorCode::InvalidLength,
+			format_args!("invalid length {}, expected {}", len, exp),
+		)
+	}
+
+	fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
+		if expected.is_empty() {
+			Self::formatted(
+				ErrorCode::UnknownVariant,
+				format_args!("unknown variant `{}`, there are no variants", variant),
+			)
+		} else {
+			Self::formatted(
+				ErrorCode::UnknownVariant,
+				format_args!("unknown variant `{}`, expected {}", variant, OneOf(expected)),
+			)
+		}
+	}
+
+	fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+		if expected.is_empty() {
+			Self::formatted(
+				ErrorCode::UnknownField,
+				format_args!("unknown field `{}`, there are no fields", field),
+			)
+		} else {
+			Self::formatted(
+				ErrorCode::UnknownField,
+				format_args!("unknown field `{}`, expected {}", field, OneOf(expected)),
+			)
+		}
+	}
+
+	fn missing_field(field: &'static str) -> Self {
+		Self::with_field(ErrorCode::MissingField, field)
+	}
+
+	fn duplicate_field(field: &'static str) -> Self {
+		Self::with_field(ErrorCode::DuplicateField, field)
+	}
+}
+
+impl serde::ser::Error for Error {
+	fn custom<T: fmt::Display>(msg: T) -> Self {
+		<Self as serde::de::Error>::custom(msg)
+	}
+}
+
+/// Lists the expected names the same way `serde` does
+struct OneOf(&'static [&'static str]);
+
+impl fmt::Display for OneOf {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.0 {
+			[] => Ok(()),
+			[a] => write!(f, "`{}`", a),
+			[a, b] => write!(f, "`{}` or `{}`", a, b),
+			[first, rest @ ..] => {
+				write!(f, "one of `{}`", first)?;
+				rest.iter().try_for_each(|name| write!(f, ", `{}`", name))
+			}
+		}
+	}
+}
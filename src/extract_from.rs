@@ -0,0 +1,89 @@
+use super::*;
+
+/// Conversion of any `Serialize` value into any `DeserializeOwned` type, through [`extract`](crate::extract)
+///
+/// It's implemented for every `B: DeserializeOwned`, with every `A: Serialize`, and can't be implemented otherwise:
+/// `B::extract_from(a)` is the same as `extract::<B, _>(a)`, written from the side of the target type. Use
+/// [`impl_from_via_extract!`](crate::impl_from_via_extract!) to get the standard `TryFrom` conversions instead.
+///
+/// ```
+/// use serde_extract::ExtractFrom;
+///
+/// #[derive(serde_derive::Serialize)]
+/// struct Dto {
+/// 	id: u32,
+/// 	name: &'static str,
+/// 	internal_flags: u64,
+/// }
+/// #[derive(serde_derive::Deserialize, Debug, PartialEq)]
+/// struct Domain {
+/// 	id: u32,
+/// 	name: String,
+/// }
+/// let dto = Dto {
+/// 	id: 1,
+/// 	name: "a",
+/// 	internal_flags: 0,
+/// };
+/// assert_eq!(
+/// 	Domain::extract_from(&dto).unwrap(),
+/// 	Domain {
+/// 		id: 1,
+/// 		name: "a".to_owned()
+/// 	}
+/// );
+/// ```
+pub trait ExtractFrom<A: ?Sized>: Sized + sealed::Sealed<A> {
+	/// Extract `Self` from `source`, see [`extract`](crate::extract)
+	fn extract_from(source: &A) -> Result<Self, Error>;
+}
+
+mod sealed {
+	pub trait Sealed<A: ?Sized> {}
+}
+
+impl<A: Serialize + ?Sized, B: DeserializeOwned> sealed::Sealed<A> for B {}
+impl<A: Serialize + ?Sized, B: DeserializeOwned> ExtractFrom<A> for B {
+	fn extract_from(source: &A) -> Result<Self, Error> {
+		extract(source)
+	}
+}
+
+/// Implements `TryFrom<&A> for B` through [`extract`](crate::extract), for each `A => B` pair
+///
+/// The `Error` of the conversion is [`Error`].
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Dto {
+/// 	id: u32,
+/// 	name: &'static str,
+/// }
+/// #[derive(serde_derive::Deserialize, Debug, PartialEq)]
+/// struct Domain {
+/// 	id: u32,
+/// 	name: String,
+/// }
+/// #[derive(serde_derive::Deserialize, Debug, PartialEq)]
+/// struct Storage {
+/// 	id: u64,
+/// }
+/// serde_extract::impl_from_via_extract!(Dto => Domain, Dto => Storage);
+///
+/// let dto = Dto { id: 1, name: "a" };
+/// assert_eq!(Storage::try_from(&dto).unwrap(), Storage { id: 1 });
+/// let domain: Domain = (&dto).try_into().unwrap();
+/// assert_eq!(domain.name, "a");
+/// ```
+#[macro_export]
+macro_rules! impl_from_via_extract {
+	($($a: ty => $b: ty),+ $(,)?) => {$(
+		impl ::core::convert::TryFrom<&$a> for $b {
+			type Error = $crate::Error;
+
+			fn try_from(source: &$a) -> ::core::result::Result<Self, Self::Error> {
+				$crate::extract(source)
+			}
+		}
+	)+};
+}
@@ -0,0 +1,224 @@
+use super::*;
+
+/// Looks for `key` in every struct and map of the value, at any depth, and extracts the values found there
+pub(crate) struct FindAllSerializer<'a, T, F> {
+	pub(crate) key: &'a str,
+	pub(crate) found: &'a mut F,
+	pub(crate) config: Config,
+	pub(crate) _spooky: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: DeserializeOwned, F: FnMut(T)> FindAllSerializer<'a, T, F> {
+	fn reborrow(&mut self) -> FindAllSerializer<'_, T, F> {
+		FindAllSerializer {
+			key: self.key,
+			found: &mut *self.found,
+			config: self.config,
+			_spooky: PhantomData,
+		}
+	}
+
+	fn compound(self) -> FindAllCompound<'a, T, F> {
+		FindAllCompound {
+			serializer: self,
+			key_matches: false,
+		}
+	}
+}
+
+macro_rules! leaves {
+	($($fn_: ident($($ty: ty),*),)*) => {$(
+		fn $fn_(self, $(_: $ty),*) -> Result<Self::Ok, Self::Error> {
+			Ok(())
+		}
+	)*};
+}
+
+impl<'a, T: DeserializeOwned, F: FnMut(T)> Serializer for FindAllSerializer<'a, T, F> {
+	type Ok = ();
+	type Error = Error;
+
+	leaves! {
+		serialize_bool(bool),
+		serialize_i8(i8),
+		serialize_i16(i16),
+		serialize_i32(i32),
+		serialize_i64(i64),
+		serialize_i128(i128),
+		serialize_u8(u8),
+		serialize_u16(u16),
+		serialize_u32(u32),
+		serialize_u64(u64),
+		serialize_u128(u128),
+		serialize_f32(f32),
+		serialize_f64(f64),
+		serialize_char(char),
+		serialize_str(&str),
+		serialize_bytes(&[u8]),
+		serialize_none(),
+		serialize_unit(),
+		serialize_unit_struct(&'static str),
+		serialize_unit_variant(&'static str, u32, &'static str),
+	}
+
+	#[cfg(not(feature = "alloc"))]
+	fn collect_str<U: ?Sized>(self, _: &U) -> Result<Self::Ok, Self::Error>
+	where
+		U: core::fmt::Display,
+	{
+		Ok(())
+	}
+
+	fn serialize_some<U: ?Sized>(self, value: &U) -> Result<Self::Ok, Self::Error>
+	where
+		U: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_struct<U: ?Sized>(self, _: &'static str, value: &U) -> Result<Self::Ok, Self::Error>
+	where
+		U: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<U: ?Sized>(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		value: &U,
+	) -> Result<Self::Ok, Self::Error>
+	where
+		U: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	type SerializeSeq = FindAllCompound<'a, T, F>;
+	fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeTuple = FindAllCompound<'a, T, F>;
+	fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeTupleStruct = FindAllCompound<'a, T, F>;
+	fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeTupleVariant = FindAllCompound<'a, T, F>;
+	fn serialize_tuple_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleVariant, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeMap = FindAllCompound<'a, T, F>;
+	fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeStruct = FindAllCompound<'a, T, F>;
+	fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeStructVariant = FindAllCompound<'a, T, F>;
+	fn serialize_struct_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeStructVariant, Self::Error> {
+		Ok(self.compound())
+	}
+}
+
+pub(crate) struct FindAllCompound<'a, T, F> {
+	serializer: FindAllSerializer<'a, T, F>,
+	/// Whether the key of the map entry being serialized is the one we're looking for
+	key_matches: bool,
+}
+
+impl<T: DeserializeOwned, F: FnMut(T)> FindAllCompound<'_, T, F> {
+	fn element<U: Serialize + ?Sized>(&mut self, value: &U) -> Result<(), Error> {
+		value.serialize(self.serializer.reborrow())
+	}
+
+	fn field<U: Serialize + ?Sized>(&mut self, is_key: bool, value: &U) -> Result<(), Error> {
+		if is_key {
+			let found = T::deserialize(DeserializerFromSerializable::with_config(value, self.serializer.config))?;
+			(self.serializer.found)(found);
+		}
+		// Like JSONPath's `$..key`, values found under the key may themselves contain the key
+		self.element(value)
+	}
+
+	fn struct_field<U: Serialize + ?Sized>(&mut self, name: &'static str, value: &U) -> Result<(), Error> {
+		let is_key = name == self.serializer.key;
+		self.field(is_key, value).map_err(|e| e.at_field(name))
+	}
+}
+
+macro_rules! find_all_compound_impls {
+	($($trait_: ident { $fn_: ident($($arg: ident: $arg_ty: ty),*) => $method: ident; })*) => {$(
+		impl<T: DeserializeOwned, F: FnMut(T)> $trait_ for FindAllCompound<'_, T, F> {
+			type Ok = ();
+			type Error = Error;
+
+			fn $fn_<U: ?Sized>(&mut self, $($arg: $arg_ty,)* value: &U) -> Result<(), Self::Error>
+			where
+				U: Serialize,
+			{
+				self.$method($($arg,)* value)
+			}
+
+			fn end(self) -> Result<Self::Ok, Self::Error> {
+				Ok(())
+			}
+		}
+	)*};
+}
+find_all_compound_impls! {
+	SerializeSeq { serialize_element() => element; }
+	SerializeTuple { serialize_element() => element; }
+	SerializeTupleStruct { serialize_field() => element; }
+	SerializeTupleVariant { serialize_field() => element; }
+	SerializeStruct { serialize_field(name: &'static str) => struct_field; }
+	SerializeStructVariant { serialize_field(name: &'static str) => struct_field; }
+}
+
+impl<T: DeserializeOwned, F: FnMut(T)> SerializeMap for FindAllCompound<'_, T, F> {
+	type Ok = ();
+	type Error = Error;
+
+	fn serialize_key<U: ?Sized>(&mut self, key: &U) -> Result<(), Self::Error>
+	where
+		U: Serialize,
+	{
+		self.key_matches = map_access_from_serizable::StringComparisonSerializer::key_is(key, self.serializer.key)?;
+		Ok(())
+	}
+
+	fn serialize_value<U: ?Sized>(&mut self, value: &U) -> Result<(), Self::Error>
+	where
+		U: Serialize,
+	{
+		let is_key = core::mem::replace(&mut self.key_matches, false);
+		self.field(is_key, value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(())
+	}
+}
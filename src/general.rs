@@ -1,182 +1,547 @@
 use super::*;
 
-pub struct SerializerFromVisitor<'de, V, const OPTION_HINT: bool> {
+/// What the [`Deserializer`] was asked for
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+	Any,
+	Option,
+	Enum {
+		variants: &'static [&'static str],
+	},
+	Char,
+	/// `str` or `string`
+	Str,
+	/// `bytes` or `byte_buf`
+	Bytes,
+	Seq,
+	/// `tuple` or `tuple_struct`
+	Tuple,
+}
+
+impl Hint {
+	/// The variants of the target enum, if that's what we were asked for
+	pub fn target_variants(self) -> Option<&'static [&'static str]> {
+		match self {
+			Hint::Enum { variants } => Some(variants),
+			_ => None,
+		}
+	}
+}
+
+/// Where a value that was looked up in the source ends up
+///
+/// This is usually a [`DeserializeSeed`], but may also be another lookup in that value.
+pub trait ValueSink<'de> {
+	type Value;
+	fn sink<T: Serialize + ?Sized>(self, value: &T, config: Config) -> Result<Self::Value, Error>;
+}
+
+impl<'de, S: DeserializeSeed<'de>> ValueSink<'de> for S {
+	type Value = S::Value;
+	fn sink<T: Serialize + ?Sized>(self, value: &T, config: Config) -> Result<Self::Value, Error> {
+		self.deserialize(DeserializerFromSerializable {
+			serializable: value,
+			config,
+		})
+	}
+}
+
+pub struct SerializerFromVisitor<'de, V> {
 	pub(crate) visitor: V,
+	pub(crate) hint: Hint,
+	pub(crate) config: Config,
 	pub(crate) _spooky: PhantomData<&'de ()>,
 }
 
-impl<'de, V: Visitor<'de>, const OPTION_HINT: bool> Serializer for SerializerFromVisitor<'de, V, OPTION_HINT> {
-	type Ok = V::Value;
+/// The kind of compound serialization that [`SerializerFromVisitor`] encountered
+#[derive(Clone, Copy)]
+enum Compound {
+	/// `seq`, `tuple` or `tuple_struct`
+	Seq,
+	/// `struct` or `map`
+	Struct,
+	/// `tuple_variant` or `struct_variant`
+	Variant,
+}
+
+/// What [`SerializerFromVisitor`] did with its visitor
+pub enum Driven<T, V> {
+	Visited(T),
+	/// The visitor wants to be given the value as a whole (e.g. through `visit_some`), which we can't do from the
+	/// inside of the serialization, so it is handed back to re-drive the `Serialize` value with it
+	Redrive {
+		visitor: V,
+		/// Number of elements of the compound value, if known
+		len: Option<usize>,
+	},
+}
+
+impl<'de, V: Visitor<'de>> SerializerFromVisitor<'de, V> {
+	fn redrive(self, compound: Compound, len: Option<usize>) -> Result<Redrive<'de, V>, Error> {
+		match (self.hint, compound) {
+			(Hint::Option, _) => {}
+			(Hint::Seq | Hint::Bytes | Hint::Tuple, Compound::Seq) => {}
+			// Tuples can be filled from the fields of a struct or the values of a map, in order
+			(Hint::Tuple, Compound::Struct) => {}
+			(Hint::Seq | Hint::Bytes | Hint::Tuple | Hint::Any | Hint::Enum { .. } | Hint::Char | Hint::Str, _) => {
+				return Err(Error::new(
+					ErrorCode::Unsupported,
+					"Deserialization from compound serialization is unsupported, \
+					unless hinted with deserialize_struct, deserialize_seq or deserialize_tuple",
+				))
+			}
+		}
+		Ok(Redrive {
+			visitor: self.visitor,
+			len,
+			_spooky: PhantomData,
+		})
+	}
+}
+
+impl<'de, V: Visitor<'de>> Serializer for SerializerFromVisitor<'de, V> {
+	type Ok = Driven<V::Value, V>;
 	type Error = Error;
 
 	fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_bool(v)
+			self.visitor.visit_bool(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_i8(v)
+			self.visitor.visit_i8(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_i16(v)
+			self.visitor.visit_i16(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_i32(v)
+			self.visitor.visit_i32(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_i64(v)
+			self.visitor.visit_i64(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_u8(v)
+			self.visitor.visit_u8(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_u16(v)
+			self.visitor.visit_u16(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_u32(v)
+			self.visitor.visit_u32(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_u64(v)
+			self.visitor.visit_u64(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_f32(v)
+			self.visitor.visit_f32(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_f64(v)
+			self.visitor.visit_f64(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
+		if self.hint == Hint::Option {
 			self.serialize_some(&v)
 		} else {
-			self.visitor.visit_char(v)
+			self.visitor.visit_char(v).map(Driven::Visited)
 		}
 	}
 
 	fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
-			self.serialize_some(&v)
-		} else {
-			self.visitor.visit_str(v)
+		match self.hint {
+			Hint::Option => self.serialize_some(&v),
+			Hint::Char => {
+				let mut chars = v.chars();
+				match (chars.next(), chars.next()) {
+					(Some(c), None) => self.visitor.visit_char(c).map(Driven::Visited),
+					_ => Err(Error::invalid_value(Unexpected::Str(v), &"a single character")),
+				}
+			}
+			_ => self.visitor.visit_str(v).map(Driven::Visited),
 		}
 	}
 
+	/// Without `alloc`, the string is written to a buffer on the stack, so it has to be small enough to fit
+	#[cfg(not(feature = "alloc"))]
+	fn collect_str<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: core::fmt::Display,
+	{
+		collect_str_on_stack(value, |v| self.serialize_str(v))
+	}
+
 	fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-		if OPTION_HINT {
-			self.serialize_some(v)
-		} else {
-			self.visitor.visit_bytes(v)
+		match self.hint {
+			// Going through `serialize_some` would turn the bytes into a seq
+			Hint::Option => Ok(Driven::Redrive {
+				visitor: self.visitor,
+				len: None,
+			}),
+			Hint::Seq | Hint::Tuple => value::SeqDeserializer::new(v.iter().copied())
+				.deserialize_any(self.visitor)
+				.map(Driven::Visited),
+			Hint::Str => match core::str::from_utf8(v) {
+				Ok(v) => self.visitor.visit_str(v).map(Driven::Visited),
+				Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &"a valid UTF-8 string")),
+			},
+			Hint::Any | Hint::Enum { .. } | Hint::Char | Hint::Bytes => {
+				self.visitor.visit_bytes(v).map(Driven::Visited)
+			}
 		}
 	}
 
 	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-		self.visitor.visit_none()
+		if self.hint == Hint::Option && self.config.present_none_as_some {
+			// The value is there and it's `None`: only an inner `Option` can be deserialized from it
+			self.visitor.visit_some(NoneDeserializer).map(Driven::Visited)
+		} else {
+			self.visitor.visit_none().map(Driven::Visited)
+		}
 	}
 
 	fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
 	where
 		T: Serialize,
 	{
-		self.visitor
-			.visit_some(DeserializerFromSerializable { serializable: value })
+		if self.hint == Hint::Option {
+			if self.config.present_none_as_some && is_none(value, self.config)? {
+				// `Some(None)` already says where the `None` is, so it isn't wrapped in another `Some`
+				self.visitor.visit_some(NoneDeserializer).map(Driven::Visited)
+			} else {
+				self.visitor
+					.visit_some(DeserializerFromSerializable {
+						serializable: value,
+						config: self.config,
+					})
+					.map(Driven::Visited)
+			}
+		} else {
+			// Like with self-describing formats, if we weren't asked for an `Option`, `Some` is transparent
+			value.serialize(self)
+		}
 	}
 
 	fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-		self.visitor.visit_unit()
+		self.visitor.visit_unit().map(Driven::Visited)
 	}
 
 	fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
-		self.visitor.visit_unit()
+		self.visitor.visit_unit().map(Driven::Visited)
 	}
 
-	fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Self::Ok, Self::Error> {
-		self.visitor.visit_borrowed_str(variant)
+	fn serialize_unit_variant(
+		self,
+		_: &'static str,
+		variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Self::Error> {
+		match self.hint {
+			Hint::Option => Ok(Driven::Redrive {
+				visitor: self.visitor,
+				len: None,
+			}),
+			Hint::Enum { variants } => {
+				let value = if self.config.variant_matching.by_index(variant, Some(variants)) {
+					self.visitor.visit_enum(value::U32Deserializer::new(variant_index))?
+				} else {
+					self.visitor.visit_enum(value::BorrowedStrDeserializer::new(variant))?
+				};
+				Ok(Driven::Visited(value))
+			}
+			Hint::Any | Hint::Char | Hint::Str | Hint::Bytes | Hint::Seq | Hint::Tuple => {
+				self.visitor.visit_borrowed_str(variant).map(Driven::Visited)
+			}
+		}
 	}
 
 	fn serialize_newtype_struct<T: ?Sized>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
 	where
 		T: Serialize,
 	{
-		if OPTION_HINT {
-			self.serialize_some(value)
+		if self.hint == Hint::Option {
+			// Going through `serialize_some` would lose the newtype
+			Ok(Driven::Redrive {
+				visitor: self.visitor,
+				len: None,
+			})
 		} else {
 			self.visitor
-				.visit_newtype_struct(DeserializerFromSerializable { serializable: value })
+				.visit_newtype_struct(DeserializerFromSerializable {
+					serializable: value,
+					config: self.config,
+				})
+				.map(Driven::Visited)
 		}
 	}
 
 	fn serialize_newtype_variant<T: ?Sized>(
 		self,
 		_: &'static str,
-		_: u32,
+		variant_index: u32,
 		variant: &'static str,
 		value: &T,
 	) -> Result<Self::Ok, Self::Error>
 	where
 		T: Serialize,
 	{
-		if OPTION_HINT {
-			self.serialize_some(value)
+		if self.hint == Hint::Option {
+			// Going through `serialize_some` would lose the variant
+			Ok(Driven::Redrive {
+				visitor: self.visitor,
+				len: None,
+			})
 		} else {
 			self.visitor
-				.visit_enum(newtype_variant::ThisEnumAccess { variant, value })
+				.visit_enum(newtype_variant::ThisEnumAccess {
+					variant,
+					variant_index,
+					target_variants: self.hint.target_variants(),
+					value,
+					config: self.config,
+				})
+				.map(Driven::Visited)
 		}
 	}
 
-	serializer_unsupported! {
-		err = (<Self::Error as serde::ser::Error>::custom("Deserialization from seq-like serialization is unsupported, unless hinted with deserialize_struct"));
-		seq tuple tuple_struct tuple_variant map struct struct_variant
+	type SerializeSeq = Redrive<'de, V>;
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+		self.redrive(Compound::Seq, len)
 	}
+
+	type SerializeTuple = Redrive<'de, V>;
+	fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		self.redrive(Compound::Seq, Some(len))
+	}
+
+	type SerializeTupleStruct = Redrive<'de, V>;
+	fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		self.redrive(Compound::Seq, Some(len))
+	}
+
+	type SerializeTupleVariant = Redrive<'de, V>;
+	fn serialize_tuple_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleVariant, Self::Error> {
+		self.redrive(Compound::Variant, None)
+	}
+
+	type SerializeMap = Redrive<'de, V>;
+	fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		self.redrive(Compound::Struct, len)
+	}
+
+	type SerializeStruct = Redrive<'de, V>;
+	fn serialize_struct(self, _: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+		self.redrive(Compound::Struct, Some(len))
+	}
+
+	type SerializeStructVariant = Redrive<'de, V>;
+	fn serialize_struct_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeStructVariant, Self::Error> {
+		self.redrive(Compound::Variant, None)
+	}
+}
+
+/// Writes `value` to a buffer on the stack, and hands the resulting string to `f`
+///
+/// This is how `collect_str` is implemented without `alloc`.
+#[cfg(not(feature = "alloc"))]
+pub(crate) fn collect_str_on_stack<T, R>(value: &T, f: impl FnOnce(&str) -> Result<R, Error>) -> Result<R, Error>
+where
+	T: core::fmt::Display + ?Sized,
+{
+	struct StackBuffer {
+		buf: [u8; 256],
+		len: usize,
+	}
+	impl core::fmt::Write for StackBuffer {
+		fn write_str(&mut self, s: &str) -> core::fmt::Result {
+			let dest = self.buf.get_mut(self.len..self.len + s.len()).ok_or(core::fmt::Error)?;
+			dest.copy_from_slice(s.as_bytes());
+			self.len += s.len();
+			Ok(())
+		}
+	}
+	let mut buffer = StackBuffer { buf: [0; 256], len: 0 };
+	core::fmt::write(&mut buffer, format_args!("{}", value)).map_err(|_| {
+		Error::new(
+			ErrorCode::Unsupported,
+			"Strings collected from `Display` can be at most 256 bytes long without the `alloc` feature",
+		)
+	})?;
+	match core::str::from_utf8(&buffer.buf[..buffer.len]) {
+		Ok(v) => f(v),
+		Err(_) => Err(Error::new(ErrorCode::Internal, "`Display` wrote invalid UTF-8")),
+	}
+}
+
+/// Whether `value` serializes as `none`
+fn is_none<T: Serialize + ?Sized>(value: &T, config: Config) -> Result<bool, Error> {
+	struct IsNone;
+	impl<'de> Visitor<'de> for IsNone {
+		type Value = bool;
+
+		fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+			formatter.write_str("anything")
+		}
+
+		fn visit_none<E>(self) -> Result<Self::Value, E> {
+			Ok(true)
+		}
+
+		fn visit_unit<E>(self) -> Result<Self::Value, E> {
+			Ok(false)
+		}
+
+		fn visit_some<D>(self, _: D) -> Result<Self::Value, D::Error>
+		where
+			D: Deserializer<'de>,
+		{
+			Ok(false)
+		}
+	}
+	DeserializerFromSerializable {
+		serializable: value,
+		config: config.present_none_as_some(false),
+	}
+	.deserialize_option(IsNone)
+}
+
+/// The contents of a present `None` under [`Config::present_none_as_some`]: only an `Option` can be deserialized from it
+struct NoneDeserializer;
+
+impl<'de> Deserializer<'de> for NoneDeserializer {
+	type Error = Error;
+
+	fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		Err(Error::new(
+			ErrorCode::InvalidType,
+			"A present `None` can only be extracted as an `Option`",
+		))
+	}
+
+	fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_none()
+	}
+
+	fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_newtype_struct(self)
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf unit unit_struct seq tuple
+		tuple_struct map struct enum identifier ignored_any
+	}
+}
+
+/// Skips through the contents of a compound value, to then hand the visitor back through [`Driven::Redrive`]
+pub struct Redrive<'de, V> {
+	visitor: V,
+	len: Option<usize>,
+	_spooky: PhantomData<&'de ()>,
+}
+
+macro_rules! redrive_impls {
+	($($trait_: ident { $($fn_: ident($($arg_ty: ty),*);)* })*) => {$(
+		impl<'de, V: Visitor<'de>> $trait_ for Redrive<'de, V> {
+			type Ok = Driven<V::Value, V>;
+			type Error = Error;
+
+			$(
+				fn $fn_<T: ?Sized>(&mut self, $(_: $arg_ty,)* _: &T) -> Result<(), Self::Error>
+				where
+					T: Serialize,
+				{
+					Ok(())
+				}
+			)*
+
+			fn end(self) -> Result<Self::Ok, Self::Error> {
+				Ok(Driven::Redrive {
+					visitor: self.visitor,
+					len: self.len,
+				})
+			}
+		}
+	)*};
+}
+redrive_impls! {
+	SerializeSeq { serialize_element(); }
+	SerializeTuple { serialize_element(); }
+	SerializeTupleStruct { serialize_field(); }
+	SerializeTupleVariant { serialize_field(); }
+	SerializeMap { serialize_key(); serialize_value(); }
+	SerializeStruct { serialize_field(&'static str); }
+	SerializeStructVariant { serialize_field(&'static str); }
 }
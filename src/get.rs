@@ -0,0 +1,28 @@
+use super::*;
+
+/// Tuples of types that can be fetched together by [`get_many`](crate::get_many)
+pub trait GetMany {
+	/// The tuple of `Option`s that is returned, e.g. `(Option<A>, Option<B>)` for `(A, B)`
+	type Output: DeserializeOwned;
+	/// The names of the fields, one per element of the tuple, e.g. `[&str; 2]` for `(A, B)`
+	type Fields<'f>: AsRef<[&'f str]>;
+}
+
+macro_rules! get_many_impls {
+	($($len: literal => ($($t: ident),+),)*) => {$(
+		impl<$($t: DeserializeOwned),+> GetMany for ($($t,)+) {
+			type Output = ($(Option<$t>,)+);
+			type Fields<'f> = [&'f str; $len];
+		}
+	)*};
+}
+get_many_impls! {
+	1 => (A),
+	2 => (A, B),
+	3 => (A, B, C),
+	4 => (A, B, C, D),
+	5 => (A, B, C, D, E),
+	6 => (A, B, C, D, E, F),
+	7 => (A, B, C, D, E, F, G),
+	8 => (A, B, C, D, E, F, G, H),
+}
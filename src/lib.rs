@@ -63,25 +63,60 @@
 //! }
 //! ```
 //!
+//! For a single field like this, [`get`] does the same without declaring `MaybePageSize`:
+//! `serde_extract::get::<usize, _>(serializable, "page_size")?`.
+//!
+//! # Features
+//!
+//! - `std` (default): enables `serde`'s `std` feature, and `alloc`
+//! - `alloc`: [`Error`] keeps formatted messages
+//!
+//! With `default-features = false`, the crate works on bare-metal targets. [`Error`] then still carries an
+//! [`ErrorCode`] and the name of the offending field. Strings that a value serializes through
+//! [`collect_str`](Serializer::collect_str) (usually its `Display` implementation) are then written to a 256-byte
+//! buffer on the stack when they're extracted or walked, and longer ones fail with [`ErrorCode::Unsupported`].
+//! Comparing them with field names or path filters doesn't have that limit.
+//!
 //! # Limitations
 //!
-//! - Sequences are not supported for now (although support could theoritically be added, algorithmic complexity of
-//!   generated code would be O(n²) where n is the number of elements in the sequence because we would need to re-drive
-//!   the [`Serializer`] for each element called for by the [`Visitor`] through [`MapAccess`])
+//! - Sequences are only supported when hinted by [`deserialize_seq`](Deserializer::deserialize_seq) (or
+//!   `deserialize_bytes`, `deserialize_tuple`...), and algorithmic complexity of generated code is O(n²) where n is
+//!   the number of elements in the sequence, because we need to re-drive the [`Serializer`] for each element called
+//!   for by the [`Visitor`] through [`SeqAccess`]
 //! - For the same reason, deserializing into `map`s is currently unsupported. Specifically, currently we can only
 //!   extract struct fields if the fields names are hinted by [`deserialize_struct`](Deserializer::deserialize_struct).
-//!   (This enables driving the [`Serializer`] only as many times as there are fields to extract. In practice if both
-//!   sides are regular structs, the optimizer probably turns that into zero-cost extraction. In theory again, support
-//!   for deserializing into maps could be added with O(n²) complexity where n is the number of input fields.)
+//!   (This enables driving the [`Serializer`] only twice per field to extract: once to check whether it's present, and
+//!   once to deserialize it. In practice if both sides are regular structs, the optimizer probably turns that into
+//!   zero-cost extraction. In theory again, support for deserializing into maps could be added with O(n²) complexity
+//!   where n is the number of input fields.)
 
 #![no_std]
+// Doc examples are indented following `hard_tabs`, and `serializer_unsupported!` expands to code that the other
+// lint complains about
+#![allow(clippy::tabs_in_doc_comments, clippy::multiple_bound_locations)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+mod config;
+mod describe;
+mod elements;
+mod entries;
+mod error;
+mod extract_from;
+mod find;
 mod general;
+mod get;
 mod map_access_from_serizable;
 mod newtype_variant;
+mod path;
+mod seq_access_from_serializable;
+mod serialize_ext;
+mod walk;
 
 use {
 	core::marker::PhantomData,
+	general::ValueSink,
 	serde::{
 		de::{Error as _, *},
 		forward_to_deserialize_any,
@@ -90,17 +125,509 @@ use {
 	serde_serializer_quick_unsupported::serializer_unsupported,
 };
 
-pub use serde::de::value::Error;
+pub use {
+	config::{Config, TupleFields, VariantFields, VariantMatching},
+	describe::Description,
+	elements::Elements,
+	entries::Entries,
+	error::{Error, ErrorCode},
+	extract_from::ExtractFrom,
+	get::GetMany,
+	path::{Path, Segment},
+	serialize_ext::SerializeExt,
+	walk::{Leaf, Node, Walk},
+};
+
+#[doc(hidden)]
+pub use path::__path_filter_literal;
+
+#[cfg(feature = "alloc")]
+pub use describe::Shape;
 
 /// Extract a `T: DeserializeOwned` from `S: Serialize`
 ///
-/// See [crate-level documentation](crate) for examples
+/// See [crate-level documentation](crate) for examples, and [Features](crate#features) for the length limit on
+/// strings collected from `Display` without the `alloc` feature
 pub fn extract<T, S>(serializable: &S) -> Result<T, Error>
 where
 	S: Serialize + ?Sized,
 	T: DeserializeOwned,
 {
-	T::deserialize(DeserializerFromSerializable { serializable })
+	T::deserialize(DeserializerFromSerializable::new(serializable))
+}
+
+/// Extract a `T: DeserializeOwned` from `S: Serialize`, using a custom [`Config`]
+///
+/// See [`Config`] for examples
+pub fn extract_with<T, S>(serializable: &S, config: Config) -> Result<T, Error>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+{
+	T::deserialize(DeserializerFromSerializable::with_config(serializable, config))
+}
+
+/// Extract the field `field` of the struct or map `S: Serialize` as a `T: DeserializeOwned`
+///
+/// This is `None` if the field is absent, or if it's `None` (or `()`).
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Request {
+/// 	field_a: &'static str,
+/// 	page_size: usize,
+/// }
+/// let request = Request {
+/// 	field_a: "hello!",
+/// 	page_size: 2,
+/// };
+/// assert_eq!(serde_extract::get::<usize, _>(&request, "page_size").unwrap(), Some(2));
+/// assert_eq!(serde_extract::get::<usize, _>(&request, "cursor").unwrap(), None);
+/// ```
+pub fn get<T, S>(serializable: &S, field: &str) -> Result<Option<T>, Error>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+{
+	serializable
+		.serialize(map_access_from_serizable::ExtractFieldByNameSerializer::new(
+			field,
+			PhantomData::<Option<T>>,
+			Config::new(),
+		))?
+		.into_option()
+		.map(Option::flatten)
+}
+
+/// Extract several fields of the struct or map `S: Serialize` at once, as with [`get`]
+///
+/// `T` is a tuple of the types of the fields, e.g. `(A, B)`, and the result is the corresponding tuple of `Option`s,
+/// e.g. `(Option<A>, Option<B>)`.
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Request {
+/// 	field_a: &'static str,
+/// 	page_size: usize,
+/// }
+/// let request = Request {
+/// 	field_a: "hello!",
+/// 	page_size: 2,
+/// };
+/// assert_eq!(
+/// 	serde_extract::get_many::<(String, usize, u32), _>(&request, ["field_a", "page_size", "cursor"]).unwrap(),
+/// 	(Some("hello!".to_owned()), Some(2), None),
+/// );
+/// ```
+pub fn get_many<'f, T, S>(serializable: &S, fields: T::Fields<'f>) -> Result<T::Output, Error>
+where
+	S: Serialize + ?Sized,
+	T: GetMany,
+{
+	extract_fields(serializable, fields.as_ref())
+}
+
+/// Extract the given fields of `S: Serialize` into a tuple-like `T: DeserializeOwned`, in the order of `fields`
+///
+/// Fields that are absent from the source are extracted as `None` if the corresponding element of `T` is an `Option`.
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Record {
+/// 	id: u32,
+/// 	name: &'static str,
+/// 	score: f64,
+/// }
+/// let record = Record { id: 1, name: "a", score: 0.5 };
+/// assert_eq!(
+/// 	(0.5, 1),
+/// 	serde_extract::extract_fields::<(f64, u32), _>(&record, &["score", "id"]).unwrap(),
+/// );
+/// ```
+pub fn extract_fields<T, S>(serializable: &S, fields: &[&str]) -> Result<T, Error>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+{
+	T::deserialize(map_access_from_serizable::FieldsDeserializer::new(
+		serializable,
+		fields,
+		Config::new(),
+	))
+}
+
+/// Iterate over the entries of a struct or map `S: Serialize`, extracting keys as `K` and values as `V`
+///
+/// Struct field names are extracted as keys.
+///
+/// ```
+/// let source = std::collections::BTreeMap::from([("a", 1u32), ("b", 2)]);
+/// let entries: Vec<(String, u64)> = serde_extract::entries(&source)
+/// 	.collect::<Result<_, _>>()
+/// 	.unwrap();
+/// assert_eq!(entries, [("a".to_owned(), 1), ("b".to_owned(), 2)]);
+/// ```
+pub fn entries<K, V, S>(serializable: &S) -> Entries<'_, S, K, V>
+where
+	S: Serialize + ?Sized,
+	K: DeserializeOwned,
+	V: DeserializeOwned,
+{
+	Entries::new(serializable, Config::new())
+}
+
+/// Iterate over the elements of a sequence `S: Serialize`, extracting them as `T`
+///
+/// ```
+/// let mut elements = serde_extract::elements::<u64, _>(&[1u8, 2, 3]);
+/// assert_eq!(elements.next().unwrap().unwrap(), 1);
+/// assert_eq!(elements.next().unwrap().unwrap(), 2);
+/// ```
+pub fn elements<T, S>(serializable: &S) -> Elements<'_, S, T>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+{
+	Elements::new(serializable, None, Config::new())
+}
+
+/// Iterate over the elements of the sequence in the `field` of `S: Serialize`, extracting them as `T`
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Source {
+/// 	items: Vec<u32>,
+/// }
+/// let source = Source { items: vec![1, 2, 3] };
+/// let sum: u32 = serde_extract::elements_at::<u32, _>(&source, "items")
+/// 	.map(Result::unwrap)
+/// 	.sum();
+/// assert_eq!(sum, 6);
+/// ```
+pub fn elements_at<'s, T, S>(serializable: &'s S, field: &'s str) -> Elements<'s, S, T>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+{
+	Elements::new(serializable, Some(field), Config::new())
+}
+
+/// Whether the struct or map `S: Serialize` has the given field
+///
+/// The value of the field is not deserialized, so this is true even if the field is `None`.
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Request {
+/// 	page_size: Option<usize>,
+/// }
+/// let request = Request { page_size: None };
+/// assert!(serde_extract::has_field(&request, "page_size").unwrap());
+/// assert!(!serde_extract::has_field(&request, "cursor").unwrap());
+/// ```
+pub fn has_field<S>(serializable: &S, field: &str) -> Result<bool, Error>
+where
+	S: Serialize + ?Sized,
+{
+	let presence = map_access_from_serizable::FieldPresence { none_as_missing: false };
+	Ok(serializable
+		.serialize(map_access_from_serizable::ExtractFieldByNameSerializer::new(
+			field,
+			presence,
+			Config::new(),
+		))?
+		.into_option()?
+		.is_some())
+}
+
+/// Whether `S: Serialize` has a value at the given path, e.g. `a.b[3].c`
+///
+/// Fields are separated by `.`, and elements of sequences are selected with `[index]`. With wildcards or filters (see
+/// [`extract_at`]), this is whether anything matches. Values are not deserialized.
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Source {
+/// 	a: Vec<Inner>,
+/// }
+/// #[derive(serde_derive::Serialize)]
+/// struct Inner {
+/// 	b: u32,
+/// }
+/// let source = Source { a: vec![Inner { b: 1 }] };
+/// assert!(serde_extract::contains_path(&source, "a[0].b").unwrap());
+/// assert!(!serde_extract::contains_path(&source, "a[1].b").unwrap());
+/// ```
+pub fn contains_path<'p, S>(serializable: &S, path: impl Path<'p>) -> Result<bool, Error>
+where
+	S: Serialize + ?Sized,
+{
+	let path = path.split();
+	path::validate(path)?;
+	let presence = map_access_from_serizable::FieldPresence { none_as_missing: false };
+	if path::may_match_several(path)? {
+		let mut first = path::FirstMatch {
+			sink: Some(presence),
+			value: None,
+		};
+		path::Matches { path, sink: &mut first }.sink(serializable, Config::new())?;
+		return Ok(first.value.is_some());
+	}
+	let sink = path::PathSink { path, sink: presence };
+	Ok(sink.sink(serializable, Config::new())?.is_some())
+}
+
+/// Extract the value at the given path of `S: Serialize`, e.g. `a.b[3].c`, into a `T: DeserializeOwned`
+///
+/// Fields are separated by `.`, and elements of sequences are selected with `[index]`. If nothing is at the path, `T`
+/// is extracted as `None` if it's an `Option`, and a [`MissingField`](ErrorCode::MissingField) error is returned
+/// otherwise.
+///
+/// The wildcards `[*]` and `.*` select every element of a sequence, or every value of a map or struct, and the filter
+/// `[field=value]` selects the elements whose `field` is equal to `value` (a number, bool, string or unit variant).
+/// Paths with wildcards or filters extract all the values they match as a sequence, so `T` should be e.g. a `Vec`,
+/// even if a single value is expected (e.g. `users[id=42].email`): use [`first_at`] to extract only the first match.
+/// Matches are found by re-driving the [`Serializer`] for every element, which is O(n²) where n is the number of
+/// matches.
+///
+/// The path may also be built with [`path!`], which checks it at compile time instead of parsing it at runtime.
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Order {
+/// 	items: Vec<Item>,
+/// }
+/// #[derive(serde_derive::Serialize)]
+/// struct Item {
+/// 	price: u32,
+/// }
+/// let order = Order {
+/// 	items: vec![Item { price: 3 }, Item { price: 5 }],
+/// };
+/// assert_eq!(serde_extract::extract_at::<u32, _>(&order, "items[1].price").unwrap(), 5);
+/// assert_eq!(serde_extract::extract_at::<Vec<u32>, _>(&order, "items[*].price").unwrap(), [3, 5]);
+/// assert_eq!(serde_extract::extract_at::<Option<u32>, _>(&order, "items[2].price").unwrap(), None);
+/// assert_eq!(serde_extract::extract_at::<Vec<u32>, _>(&order, "items[price=5].price").unwrap(), [5]);
+/// ```
+pub fn extract_at<'p, T, S>(serializable: &S, path: impl Path<'p>) -> Result<T, Error>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+{
+	let path = path.split();
+	path::validate(path)?;
+	let config = Config::new();
+	if path::may_match_several(path)? {
+		return T::deserialize(path::MatchesDeserializer {
+			serializable,
+			path,
+			config,
+		});
+	}
+	let sink = path::PathSink {
+		path,
+		sink: PhantomData::<T>,
+	};
+	match sink.sink(serializable, config)? {
+		Some(value) => Ok(value),
+		None => T::deserialize(map_access_from_serizable::MissingFieldDeserializer {
+			error: Error::new(ErrorCode::MissingField, "Nothing found at path"),
+		}),
+	}
+}
+
+/// Extract the first value that the given path of `S: Serialize` matches into a `T: DeserializeOwned`, or `None` if it
+/// doesn't match anything
+///
+/// The path is the same as for [`extract_at`], but a path with wildcards or filters only extracts its first match, e.g.
+/// to pick the element whose `id` is a given value. The source isn't searched past that match.
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Response {
+/// 	users: Vec<User>,
+/// }
+/// #[derive(serde_derive::Serialize)]
+/// struct User {
+/// 	id: u32,
+/// 	email: &'static str,
+/// }
+/// let response = Response {
+/// 	users: vec![User { id: 7, email: "a@b.c" }, User { id: 42, email: "d@e.f" }],
+/// };
+/// let email = serde_extract::first_at::<String, _>(&response, "users[id=42].email").unwrap();
+/// assert_eq!(email.as_deref(), Some("d@e.f"));
+/// assert_eq!(serde_extract::first_at::<String, _>(&response, "users[id=1].email").unwrap(), None);
+/// ```
+pub fn first_at<'p, T, S>(serializable: &S, path: impl Path<'p>) -> Result<Option<T>, Error>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+{
+	let path = path.split();
+	path::validate(path)?;
+	let config = Config::new();
+	if path::may_match_several(path)? {
+		let mut first = path::FirstMatch {
+			sink: Some(PhantomData::<T>),
+			value: None,
+		};
+		path::Matches { path, sink: &mut first }.sink(serializable, config)?;
+		return Ok(first.value);
+	}
+	path::PathSink {
+		path,
+		sink: PhantomData::<T>,
+	}
+	.sink(serializable, config)
+}
+
+/// Extract every value stored under `key` in `S: Serialize`, at any depth, like JSONPath's `$..key`
+///
+/// Structs, maps, enum variants, options and sequences are searched, in the order in which they are serialized.
+/// Values found under `key` are searched as well.
+/// Extraction stops at the first value that can't be extracted as `T`.
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Response {
+/// 	id: u32,
+/// 	items: Vec<Item>,
+/// }
+/// #[derive(serde_derive::Serialize)]
+/// struct Item {
+/// 	id: u32,
+/// }
+/// let response = Response {
+/// 	id: 1,
+/// 	items: vec![Item { id: 2 }, Item { id: 3 }],
+/// };
+/// assert_eq!(serde_extract::find_all::<u32, _>(&response, "id").unwrap(), [1, 2, 3]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn find_all<T, S>(serializable: &S, key: &str) -> Result<alloc::vec::Vec<T>, Error>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+{
+	let mut found = alloc::vec::Vec::new();
+	find_all_with(serializable, key, |value| found.push(value))?;
+	Ok(found)
+}
+
+/// Call `found` with every value stored under `key` in `S: Serialize`, at any depth
+///
+#[cfg_attr(
+	feature = "alloc",
+	doc = "This is the same as [`find_all`], without collecting the values, so it's available without the `alloc` feature."
+)]
+#[cfg_attr(
+	not(feature = "alloc"),
+	doc = "This is the same as `find_all`, without collecting the values, so it's available without the `alloc` feature."
+)]
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Response {
+/// 	id: u32,
+/// 	parent: Option<Box<Response>>,
+/// }
+/// let response = Response {
+/// 	id: 1,
+/// 	parent: Some(Box::new(Response { id: 2, parent: None })),
+/// };
+/// let mut sum = 0;
+/// serde_extract::find_all_with(&response, "id", |id: u32| sum += id).unwrap();
+/// assert_eq!(sum, 3);
+/// ```
+pub fn find_all_with<T, S, F>(serializable: &S, key: &str, mut found: F) -> Result<(), Error>
+where
+	S: Serialize + ?Sized,
+	T: DeserializeOwned,
+	F: FnMut(T),
+{
+	serializable.serialize(find::FindAllSerializer {
+		key,
+		found: &mut found,
+		config: Config::new(),
+		_spooky: PhantomData,
+	})
+}
+
+/// Describe the structure of `S: Serialize`, following the serde data model
+///
+/// Struct names and field names, map keys, sequence lengths, enum and variant names and the kind of each primitive are
+/// captured by walking the value. Displaying the [`Description`] doesn't allocate.
+#[cfg_attr(
+	feature = "alloc",
+	doc = "With the `alloc` feature, [`Description::shape`] returns it as an owned [`Shape`] tree."
+)]
+#[cfg_attr(
+	not(feature = "alloc"),
+	doc = "With the `alloc` feature, `Description::shape` returns it as an owned `Shape` tree."
+)]
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Request {
+/// 	page_size: Option<u64>,
+/// 	ids: Vec<u32>,
+/// }
+/// let request = Request {
+/// 	page_size: Some(10),
+/// 	ids: vec![1, 2],
+/// };
+/// assert_eq!(
+/// 	serde_extract::describe(&request).to_string(),
+/// 	"Request { page_size: Some(u64), ids: [u32, u32] }",
+/// );
+/// ```
+pub fn describe<S>(serializable: &S) -> Description<'_, S>
+where
+	S: Serialize + ?Sized,
+{
+	Description::new(serializable)
+}
+
+/// Walk through the structure of `S: Serialize`, calling `walker` for every node and leaf
+///
+/// This allows traversing arbitrary `Serialize` values without a format crate. Without the `alloc` feature, strings
+/// collected from `Display` are limited to 256 bytes, see [Features](crate#features).
+///
+/// ```
+/// #[derive(serde_derive::Serialize)]
+/// struct Request {
+/// 	query: String,
+/// 	ids: Vec<u32>,
+/// }
+/// #[derive(Default)]
+/// struct Measure {
+/// 	leaves: usize,
+/// 	string_bytes: usize,
+/// }
+/// impl serde_extract::Walk for Measure {
+/// 	fn leaf(&mut self, leaf: serde_extract::Leaf<'_>) -> Result<(), serde_extract::Error> {
+/// 		self.leaves += 1;
+/// 		if let serde_extract::Leaf::Str(s) = leaf {
+/// 			self.string_bytes += s.len();
+/// 		}
+/// 		Ok(())
+/// 	}
+/// }
+/// let request = Request {
+/// 	query: "abc".to_owned(),
+/// 	ids: vec![1, 2],
+/// };
+/// let mut measure = Measure::default();
+/// serde_extract::walk(&request, &mut measure).unwrap();
+/// assert_eq!((measure.leaves, measure.string_bytes), (3, 3));
+/// ```
+pub fn walk<S, W>(serializable: &S, walker: &mut W) -> Result<(), Error>
+where
+	S: Serialize + ?Sized,
+	W: Walk + ?Sized,
+{
+	serializable.serialize(walk::WalkSerializer { walker })
 }
 
 /// Our serializer that can be built from a type that implements `Serialize`
@@ -111,11 +638,49 @@ where
 /// fail.
 pub struct DeserializerFromSerializable<'s, S: Serialize + ?Sized> {
 	serializable: &'s S,
+	config: Config,
 }
 
 impl<'s, S: Serialize + ?Sized> DeserializerFromSerializable<'s, S> {
 	pub fn new(serializable: &'s S) -> Self {
-		Self { serializable }
+		Self::with_config(serializable, Config::new())
+	}
+
+	pub fn with_config(serializable: &'s S, config: Config) -> Self {
+		Self { serializable, config }
+	}
+
+	fn drive<'de, V: Visitor<'de>>(self, hint: general::Hint, visitor: V) -> Result<V::Value, Error> {
+		match self.serializable.serialize(general::SerializerFromVisitor {
+			visitor,
+			hint,
+			config: self.config,
+			_spooky: PhantomData,
+		})? {
+			general::Driven::Visited(value) => Ok(value),
+			general::Driven::Redrive { visitor, len } => match hint {
+				general::Hint::Option => visitor.visit_some(self),
+				general::Hint::Seq | general::Hint::Bytes | general::Hint::Tuple => visitor.visit_seq(
+					seq_access_from_serializable::ThisSeqAccess::new(self.serializable, len, self.config),
+				),
+				general::Hint::Any | general::Hint::Enum { .. } | general::Hint::Char | general::Hint::Str => {
+					Err(Error::new(
+						ErrorCode::Internal,
+						"SerializerFromVisitor should only ask for re-driving under the option, seq and tuple hints",
+					))
+				}
+			},
+		}
+	}
+}
+
+/// Allows using it with the deserializers of [`serde::de::value`], e.g. as the values of a
+/// [`MapDeserializer`](serde::de::value::MapDeserializer)
+impl<'de, S: Serialize + ?Sized> IntoDeserializer<'de, Error> for DeserializerFromSerializable<'_, S> {
+	type Deserializer = Self;
+
+	fn into_deserializer(self) -> Self::Deserializer {
+		self
 	}
 }
 
@@ -126,39 +691,105 @@ impl<'de, S: Serialize + ?Sized> Deserializer<'de> for DeserializerFromSerializa
 	where
 		V: Visitor<'de>,
 	{
-		self.serializable.serialize(general::SerializerFromVisitor::<_, false> {
-			visitor,
-			_spooky: PhantomData,
-		})
+		self.drive(general::Hint::Any, visitor)
 	}
 
 	fn deserialize_struct<V>(
 		self,
-		_: &'static str,
+		name: &'static str,
 		fields: &'static [&'static str],
 		visitor: V,
 	) -> Result<V::Value, Self::Error>
 	where
 		V: Visitor<'de>,
 	{
-		visitor.visit_map(map_access_from_serizable::ThisMapAccess::new(self.serializable, fields))
+		visitor.visit_map(map_access_from_serizable::ThisMapAccess::new(
+			self.serializable,
+			name,
+			fields,
+			self.config,
+		))
 	}
 
 	fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
 	where
 		V: Visitor<'de>,
 	{
-		self.serializable.serialize(general::SerializerFromVisitor::<_, true> {
-			visitor,
-			_spooky: PhantomData,
-		})
+		self.drive(general::Hint::Option, visitor)
+	}
+
+	fn deserialize_enum<V>(
+		self,
+		_: &'static str,
+		variants: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Enum { variants }, visitor)
+	}
+
+	fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Char, visitor)
+	}
+
+	fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Str, visitor)
+	}
+
+	fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Str, visitor)
+	}
+
+	fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Bytes, visitor)
+	}
+
+	fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Bytes, visitor)
+	}
+
+	fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Seq, visitor)
+	}
+
+	fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Tuple, visitor)
+	}
+
+	fn deserialize_tuple_struct<V>(self, _: &'static str, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.drive(general::Hint::Tuple, visitor)
 	}
 
-	// For now we'll ignore any hint except struct and just propagate what the serializer gives us
+	// For now we'll ignore any other hint and just propagate what the serializer gives us
 	// this may be improved in the future on an as-needed basis
 	forward_to_deserialize_any! {
-		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-		bytes byte_buf unit unit_struct newtype_struct seq tuple
-		tuple_struct map enum identifier ignored_any
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64
+		unit unit_struct newtype_struct map identifier ignored_any
 	}
 }
@@ -2,18 +2,37 @@ use super::*;
 
 pub struct ThisMapAccess<'s, S: Serialize + ?Sized> {
 	serializable: &'s S,
+	struct_name: &'static str,
+	/// Fields of the target struct that remain to be looked up, starting with the current one
 	fields: &'static [&'static str],
+	/// Index of `fields[0]` in the fields of the target struct
+	field_idx: usize,
 	start_idx: usize,
+	config: Config,
 }
 
 impl<'s, S: Serialize + ?Sized> ThisMapAccess<'s, S> {
-	pub(super) fn new(serializable: &'s S, fields: &'static [&'static str]) -> Self {
+	pub(super) fn new(
+		serializable: &'s S,
+		struct_name: &'static str,
+		fields: &'static [&'static str],
+		config: Config,
+	) -> Self {
 		Self {
 			serializable,
+			struct_name,
 			fields,
+			field_idx: 0,
 			start_idx: 0,
+			config,
 		}
 	}
+
+	fn skip_to_next_field(&mut self) {
+		self.fields = &self.fields[1..];
+		self.field_idx += 1;
+		self.start_idx = 0;
+	}
 }
 
 impl<'de, 's, S: Serialize + ?Sized> MapAccess<'de> for ThisMapAccess<'s, S> {
@@ -23,33 +42,66 @@ impl<'de, 's, S: Serialize + ?Sized> MapAccess<'de> for ThisMapAccess<'s, S> {
 	where
 		K: DeserializeSeed<'de>,
 	{
-		self.fields
-			.first()
-			.map(|&field_name| seed.deserialize(value::BorrowedStrDeserializer::new(field_name)))
-			.transpose()
+		// Fields that are absent from the source are skipped, so that the visitor handles them as it would for any
+		// other format (`#[serde(default)]`, `None` for options, `missing_field` error otherwise)
+		while let Some(&field_name) = self.fields.first() {
+			match self
+				.serializable
+				.serialize(ExtractFieldByNameSerializer {
+					key_to_find: field_name,
+					vseed: FieldPresence {
+						none_as_missing: self.config.none_as_missing,
+					},
+					field_idx: self.field_idx,
+					start_idx: self.start_idx,
+					struct_name: self.struct_name,
+					config: self.config,
+					_spooky: PhantomData,
+				})
+				.map_err(|e| e.at_field(field_name))?
+				.into_option()?
+			{
+				Some(true) => {
+					return seed
+						.deserialize(value::BorrowedStrDeserializer::new(field_name))
+						.map(Some);
+				}
+				Some(false) | None => self.skip_to_next_field(),
+			}
+		}
+		Ok(None)
 	}
 
 	fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
 	where
 		V: DeserializeSeed<'de>,
 	{
-		match self.serializable.serialize(ExtractFieldByNameSerializer {
-			key_to_find: self
-				.fields
-				.first()
-				.ok_or_else(|| Error::custom("Called next_value_seed without next_key_seed"))?,
-			vseed: seed,
-			start_idx: self.start_idx,
-		})? {
-			ExtractFieldByNameState::NotSeen(seed) | ExtractFieldByNameState::ShouldTakeNext(seed) => {
-				// If it's an option this will `visit_none`
-				self.fields = &self.fields[1..];
-				self.start_idx = 0;
-				seed.deserialize(value::UnitDeserializer::new())
+		let field_name = *self
+			.fields
+			.first()
+			.ok_or_else(|| Error::new(ErrorCode::Internal, "Called next_value_seed without next_key_seed"))?;
+		match self
+			.serializable
+			.serialize(ExtractFieldByNameSerializer {
+				key_to_find: field_name,
+				vseed: seed,
+				field_idx: self.field_idx,
+				start_idx: self.start_idx,
+				struct_name: self.struct_name,
+				config: self.config,
+				_spooky: PhantomData,
+			})
+			.map_err(|e| e.at_field(field_name))?
+		{
+			ExtractFieldByNameState::NotSeen(_) | ExtractFieldByNameState::ShouldTakeNext(_) => {
+				// next_key_seed has seen it before
+				Err(Error::new(
+					ErrorCode::Unsupported,
+					"Inconsistent serialization is not supported",
+				))
 			}
 			ExtractFieldByNameState::Seen(value) => {
-				self.fields = &self.fields[1..];
-				self.start_idx = 0;
+				self.skip_to_next_field();
 				Ok(value)
 			}
 			ExtractFieldByNameState::SeenAndMoreOfTheSameAreAvailable {
@@ -59,12 +111,10 @@ impl<'de, 's, S: Serialize + ?Sized> MapAccess<'de> for ThisMapAccess<'s, S> {
 				self.start_idx = first_next_available;
 				Ok(value)
 			}
-			ExtractFieldByNameState::Broken => {
-				return Err(Error::custom(
-					"Should not happen unless we exited with an error\
-            in which case we shouldn't reach this path",
-				))
-			}
+			ExtractFieldByNameState::Broken => Err(Error::new(
+				ErrorCode::Internal,
+				"Should not happen unless we exited with an error, in which case we shouldn't reach this path",
+			)),
 		}
 	}
 
@@ -73,23 +123,36 @@ impl<'de, 's, S: Serialize + ?Sized> MapAccess<'de> for ThisMapAccess<'s, S> {
 		K: DeserializeSeed<'de>,
 		V: DeserializeSeed<'de>,
 	{
+		if self.config.none_as_missing {
+			// Fields that are there may still be considered as missing, so we need to check that first
+			return Ok(match self.next_key_seed(kseed)? {
+				Some(key) => Some((key, self.next_value_seed(vseed)?)),
+				None => None,
+			});
+		}
 		Ok(loop {
 			break match self.fields.first() {
 				Some(&field_name) => {
-					match self.serializable.serialize(ExtractFieldByNameSerializer {
-						key_to_find: field_name,
-						vseed,
-						start_idx: self.start_idx,
-					})? {
+					match self
+						.serializable
+						.serialize(ExtractFieldByNameSerializer {
+							key_to_find: field_name,
+							vseed,
+							field_idx: self.field_idx,
+							start_idx: self.start_idx,
+							struct_name: self.struct_name,
+							config: self.config,
+							_spooky: PhantomData,
+						})
+						.map_err(|e| e.at_field(field_name))?
+					{
 						ExtractFieldByNameState::NotSeen(seed) | ExtractFieldByNameState::ShouldTakeNext(seed) => {
 							vseed = seed;
-							self.fields = &self.fields[1..];
-							self.start_idx = 0;
+							self.skip_to_next_field();
 							continue;
 						}
 						ExtractFieldByNameState::Seen(value) => {
-							self.fields = &self.fields[1..];
-							self.start_idx = 0;
+							self.skip_to_next_field();
 							Some((
 								kseed.deserialize(value::BorrowedStrDeserializer::new(field_name))?,
 								value,
@@ -106,9 +169,9 @@ impl<'de, 's, S: Serialize + ?Sized> MapAccess<'de> for ThisMapAccess<'s, S> {
 							))
 						}
 						ExtractFieldByNameState::Broken => {
-							return Err(Error::custom(
-								"Should not happen unless we exited with an error\
-                            in which case we shouldn't reach this path",
+							return Err(Error::new(
+								ErrorCode::Internal,
+								"Should not happen unless we exited with an error, in which case we shouldn't reach this path",
 							))
 						}
 					}
@@ -117,12 +180,179 @@ impl<'de, 's, S: Serialize + ?Sized> MapAccess<'de> for ThisMapAccess<'s, S> {
 			};
 		})
 	}
+
+	fn size_hint(&self) -> Option<usize> {
+		// `fields` only holds the remaining fields. This is an upper bound: those that are absent from the source will
+		// be skipped
+		Some(self.fields.len())
+	}
+}
+
+/// Presents the given fields of the source as a sequence, for [`extract_fields`](crate::extract_fields)
+pub struct FieldsDeserializer<'s, 'f, S: Serialize + ?Sized> {
+	serializable: &'s S,
+	fields: &'f [&'f str],
+	config: Config,
+}
+
+impl<'s, 'f, S: Serialize + ?Sized> FieldsDeserializer<'s, 'f, S> {
+	pub(super) fn new(serializable: &'s S, fields: &'f [&'f str], config: Config) -> Self {
+		Self {
+			serializable,
+			fields,
+			config,
+		}
+	}
+}
+
+impl<'de, S: Serialize + ?Sized> Deserializer<'de> for FieldsDeserializer<'_, '_, S> {
+	type Error = Error;
+
+	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_seq(FieldsSeqAccess {
+			serializable: self.serializable,
+			fields: self.fields,
+			field_idx: 0,
+			config: self.config,
+		})
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+		unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+	}
+}
+
+struct FieldsSeqAccess<'s, 'f, S: Serialize + ?Sized> {
+	serializable: &'s S,
+	fields: &'f [&'f str],
+	/// Index of `fields[0]` in the fields that were asked for
+	field_idx: usize,
+	config: Config,
+}
+
+impl<'de, S: Serialize + ?Sized> SeqAccess<'de> for FieldsSeqAccess<'_, '_, S> {
+	type Error = Error;
+
+	fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+	where
+		T: DeserializeSeed<'de>,
+	{
+		let Some((&field_name, rest)) = self.fields.split_first() else {
+			return Ok(None);
+		};
+		let value = match self
+			.serializable
+			.serialize(ExtractFieldByNameSerializer {
+				key_to_find: field_name,
+				vseed: seed,
+				field_idx: self.field_idx,
+				start_idx: 0,
+				struct_name: "",
+				config: self.config,
+				_spooky: PhantomData,
+			})?
+			.into_value_or_seed()?
+		{
+			Ok(value) => value,
+			Err(seed) => seed.deserialize(MissingFieldDeserializer {
+				error: Error::formatted(ErrorCode::MissingField, format_args!("missing field `{}`", field_name)),
+			})?,
+		};
+		self.fields = rest;
+		self.field_idx += 1;
+		Ok(Some(value))
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.fields.len())
+	}
+}
+
+/// Stands for a field that is absent from the source: `None` if an `Option` is asked for, `error` otherwise
+pub(super) struct MissingFieldDeserializer {
+	pub(super) error: Error,
 }
 
-pub struct ExtractFieldByNameSerializer<'de, S> {
-	key_to_find: &'de str,
+impl<'de> Deserializer<'de> for MissingFieldDeserializer {
+	type Error = Error;
+
+	fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		Err(self.error)
+	}
+
+	fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_none()
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+		unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+	}
+}
+
+/// Seed that doesn't deserialize anything, used to check whether a field is present
+pub struct FieldPresence {
+	/// Whether a field that serializes as `none` or `unit` should be considered absent
+	pub(super) none_as_missing: bool,
+}
+impl<'de> ValueSink<'de> for FieldPresence {
+	type Value = bool;
+	fn sink<T: Serialize + ?Sized>(self, value: &T, config: Config) -> Result<Self::Value, Error> {
+		if self.none_as_missing {
+			// A `None` is missing whether or not it would be presented as `Some(None)`
+			DeserializerFromSerializable {
+				serializable: value,
+				config: config.present_none_as_some(false),
+			}
+			.deserialize_option(self)
+		} else {
+			Ok(true)
+		}
+	}
+}
+impl<'de> Visitor<'de> for FieldPresence {
+	type Value = bool;
+
+	fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+		formatter.write_str("anything")
+	}
+
+	fn visit_none<E>(self) -> Result<Self::Value, E> {
+		Ok(false)
+	}
+
+	fn visit_unit<E>(self) -> Result<Self::Value, E> {
+		Ok(false)
+	}
+
+	fn visit_some<D>(self, _: D) -> Result<Self::Value, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Ok(true)
+	}
+}
+
+pub struct ExtractFieldByNameSerializer<'k, 'de, S> {
+	key_to_find: &'k str,
+	/// Index of the field to find in the fields of the target struct
+	field_idx: usize,
 	vseed: S,
 	start_idx: usize,
+	/// Name of the struct we are extracting into, to be matched against variant names
+	struct_name: &'static str,
+	config: Config,
+	_spooky: PhantomData<&'de ()>,
 }
 pub enum ExtractFieldByNameState<Seed, Val> {
 	NotSeen(Seed),
@@ -135,92 +365,356 @@ pub enum ExtractFieldByNameState<Seed, Val> {
 	},
 	Broken,
 }
-impl<'de, S: DeserializeSeed<'de>> Serializer for ExtractFieldByNameSerializer<'de, S> {
+
+impl<Seed, Val> ExtractFieldByNameState<Seed, Val> {
+	/// The value if the field was seen, or the seed that wasn't used otherwise
+	pub(super) fn into_value_or_seed(self) -> Result<Result<Val, Seed>, Error> {
+		match self {
+			ExtractFieldByNameState::Seen(value)
+			| ExtractFieldByNameState::SeenAndMoreOfTheSameAreAvailable { value, .. } => Ok(Ok(value)),
+			ExtractFieldByNameState::NotSeen(seed) | ExtractFieldByNameState::ShouldTakeNext(seed) => Ok(Err(seed)),
+			ExtractFieldByNameState::Broken => Err(Error::new(
+				ErrorCode::Internal,
+				"Should not happen unless we exited with an error, in which case we shouldn't reach this path",
+			)),
+		}
+	}
+
+	/// The value if the field was seen
+	pub(super) fn into_option(self) -> Result<Option<Val>, Error> {
+		self.into_value_or_seed().map(Result::ok)
+	}
+}
+
+#[allow(deprecated)] // `serializer_unsupported!` expands to the deprecated `serde_if_integer128!`
+impl<'k, 'de, S: ValueSink<'de>> Serializer for ExtractFieldByNameSerializer<'k, 'de, S> {
 	type Ok = ExtractFieldByNameState<S, S::Value>;
 	type Error = Error;
 
-	type SerializeMap = ExtractFieldByNameSerializeStructOrMap<'de, S>;
+	type SerializeMap = ExtractFieldByNameSerializeStructOrMap<'k, 'de, S>;
 	fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-		Ok(ExtractFieldByNameSerializeStructOrMap {
-			key_to_find: self.key_to_find,
-			start_idx: self.start_idx,
-			state: ExtractFieldByNameState::NotSeen(self.vseed),
-			current_idx: 0,
-		})
+		Ok(self.into_fields_serializer(None))
 	}
 
-	type SerializeStruct = ExtractFieldByNameSerializeStructOrMap<'de, S>;
+	type SerializeStruct = ExtractFieldByNameSerializeStructOrMap<'k, 'de, S>;
 	fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
-		Ok(ExtractFieldByNameSerializeStructOrMap {
+		Ok(self.into_fields_serializer(None))
+	}
+
+	type SerializeStructVariant = ExtractFieldByNameSerializeStructOrMap<'k, 'de, S>;
+	fn serialize_struct_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		variant: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeStructVariant, Self::Error> {
+		self.check_variant_fields_allowed(variant)?;
+		Ok(self.into_fields_serializer(None))
+	}
+
+	type SerializeTupleVariant = ExtractFieldByNameSerializeStructOrMap<'k, 'de, S>;
+	fn serialize_tuple_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		variant: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleVariant, Self::Error> {
+		self.check_variant_fields_allowed(variant)?;
+		let position_to_find = self.position_to_find();
+		Ok(self.into_fields_serializer(position_to_find))
+	}
+
+	type SerializeTuple = ExtractFieldByNameSerializeStructOrMap<'k, 'de, S>;
+	fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		self.check_tuple_fields_allowed()?;
+		let position_to_find = self.position_to_find();
+		Ok(self.into_fields_serializer(position_to_find))
+	}
+
+	type SerializeTupleStruct = ExtractFieldByNameSerializeStructOrMap<'k, 'de, S>;
+	fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		self.check_tuple_fields_allowed()?;
+		let position_to_find = self.position_to_find();
+		Ok(self.into_fields_serializer(position_to_find))
+	}
+
+	// Wrappers are transparent: we look for the field in what they wrap
+
+	fn serialize_newtype_struct<T: ?Sized>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		// There's nothing in there so every field is missing
+		Ok(ExtractFieldByNameState::NotSeen(self.vseed))
+	}
+
+	fn serialize_newtype_variant<T: ?Sized>(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		if self.config.look_through_newtype_variants {
+			value.serialize(self)
+		} else {
+			Err(Error::new(
+				ErrorCode::Unsupported,
+				"Can only extract from map and struct (enable `Config::look_through_newtype_variants` \
+				to extract from newtype variants)",
+			))
+		}
+	}
+
+	serializer_unsupported! {
+		err = (Error::new(ErrorCode::Unsupported, "Can only extract from map and struct"));
+		bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str bytes unit unit_struct
+		unit_variant seq i128 u128
+	}
+
+	#[cfg(not(feature = "alloc"))]
+	fn collect_str<T: ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: core::fmt::Display,
+	{
+		Err(Error::new(
+			ErrorCode::Unsupported,
+			"Can only extract from map and struct",
+		))
+	}
+}
+
+impl<'k, 'de, S: ValueSink<'de>> ExtractFieldByNameSerializer<'k, 'de, S> {
+	fn into_fields_serializer(
+		self,
+		position_to_find: Option<usize>,
+	) -> ExtractFieldByNameSerializeStructOrMap<'k, 'de, S> {
+		ExtractFieldByNameSerializeStructOrMap {
 			key_to_find: self.key_to_find,
+			position_to_find,
 			start_idx: self.start_idx,
 			state: ExtractFieldByNameState::NotSeen(self.vseed),
 			current_idx: 0,
-		})
+			config: self.config,
+		}
+	}
+}
+
+/// Position named by a field such as `"0"`, `"1"`...: other spellings of the number such as `"01"` or `"+1"` are
+/// different names
+fn parse_position(name: &str) -> Option<usize> {
+	match name.as_bytes() {
+		[b'0'] => Some(0),
+		[b'1'..=b'9', rest @ ..] if rest.iter().all(u8::is_ascii_digit) => name.parse().ok(),
+		_ => None,
 	}
+}
 
-	serializer_unsupported! {
-		err = (<Self::Error as serde::ser::Error>::custom("Can only extract from map and struct"));
-		bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str bytes none some unit unit_struct
-		unit_variant newtype_struct newtype_variant seq tuple tuple_struct tuple_variant
-		struct_variant i128 u128
+impl<'k, 'de, S> ExtractFieldByNameSerializer<'k, 'de, S> {
+	/// Looks up `key_to_find` in the source, as a field that isn't part of a target struct
+	pub(super) fn new(key_to_find: &'k str, vseed: S, config: Config) -> Self {
+		Self {
+			key_to_find,
+			field_idx: 0,
+			vseed,
+			start_idx: 0,
+			struct_name: "",
+			config,
+			_spooky: PhantomData,
+		}
+	}
+
+	/// Position of the field in tuple-like sources
+	fn position_to_find(&self) -> Option<usize> {
+		match self.config.tuple_fields {
+			TupleFields::ByPosition => Some(self.field_idx),
+			TupleFields::Rejected | TupleFields::ByNumericName => parse_position(self.key_to_find),
+		}
+	}
+
+	fn check_tuple_fields_allowed(&self) -> Result<(), Error> {
+		match self.config.tuple_fields {
+			TupleFields::Rejected => Err(Error::new(
+				ErrorCode::Unsupported,
+				"Can only extract from map and struct (set `Config::tuple_fields` \
+				to extract from tuples and tuple structs)",
+			)),
+			TupleFields::ByNumericName | TupleFields::ByPosition => Ok(()),
+		}
+	}
+
+	fn check_variant_fields_allowed(&self, variant: &str) -> Result<(), Error> {
+		match self.config.variant_fields {
+			VariantFields::AnyVariant => Ok(()),
+			VariantFields::MatchingName if variant == self.struct_name => Ok(()),
+			VariantFields::MatchingName => Err(Error::formatted(
+				ErrorCode::Unsupported,
+				format_args!("Can't extract struct `{}` from variant `{}`", self.struct_name, variant),
+			)),
+			VariantFields::Rejected => Err(Error::new(
+				ErrorCode::Unsupported,
+				"Can only extract from map and struct (set `Config::variant_fields` \
+				to extract from struct and tuple variants)",
+			)),
+		}
 	}
 }
 
-pub struct ExtractFieldByNameSerializeStructOrMap<'de, S: DeserializeSeed<'de>> {
-	key_to_find: &'de str,
+pub struct ExtractFieldByNameSerializeStructOrMap<'k, 'de, S: ValueSink<'de>> {
+	key_to_find: &'k str,
+	/// For tuple-like sources, whose fields are identified by their position
+	position_to_find: Option<usize>,
 	state: ExtractFieldByNameState<S, S::Value>,
 	current_idx: usize,
 	start_idx: usize,
+	config: Config,
 }
 
-impl<'de, S: DeserializeSeed<'de>> SerializeStruct for ExtractFieldByNameSerializeStructOrMap<'de, S> {
-	type Ok = ExtractFieldByNameState<S, S::Value>;
-	type Error = Error;
-
-	fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+impl<'k, 'de, S: ValueSink<'de>> ExtractFieldByNameSerializeStructOrMap<'k, 'de, S> {
+	fn serialize_field_if<T: ?Sized>(&mut self, is_key_to_find: bool, value: &T) -> Result<(), Error>
 	where
 		T: Serialize,
 	{
 		if self.current_idx >= self.start_idx {
-			if key == self.key_to_find {
+			if is_key_to_find {
 				self.state = match core::mem::replace(&mut self.state, ExtractFieldByNameState::Broken) {
-					ExtractFieldByNameState::NotSeen(seed) => ExtractFieldByNameState::Seen(
-						seed.deserialize(DeserializerFromSerializable { serializable: value })?,
-					),
+					ExtractFieldByNameState::NotSeen(seed) => {
+						ExtractFieldByNameState::Seen(seed.sink(value, self.config)?)
+					}
 					ExtractFieldByNameState::Seen(value) => ExtractFieldByNameState::SeenAndMoreOfTheSameAreAvailable {
 						value,
 						first_next_available: self.current_idx,
 					},
 					more_of_same @ ExtractFieldByNameState::SeenAndMoreOfTheSameAreAvailable { .. } => more_of_same,
 					ExtractFieldByNameState::Broken => {
-						return Err(Error::custom(
+						return Err(Error::new(
+							ErrorCode::Internal,
 							"ExtractFieldByNameState shouldn't be left in Broken \
                         state unless we exited with an error, \
                         in which case we expect this function to not be called again",
 						))
 					}
 					ExtractFieldByNameState::ShouldTakeNext(_) => {
-						return Err(Error::custom(
+						return Err(Error::new(
+							ErrorCode::Internal,
 							"ExtractFieldByNameState should never enter ShouldTakeNext state \
-                            through SerializeStruct",
+                            when keys are serialized along with their value",
 						))
 					}
 				}
 			} else if self.start_idx != 0 && self.current_idx == self.start_idx {
-				return Err(Error::custom("Inconsistent serialization is not supported"));
+				return Err(Error::new(
+					ErrorCode::Unsupported,
+					"Inconsistent serialization is not supported",
+				));
 			}
 		}
 		self.current_idx += 1;
 		Ok(())
 	}
 
+	fn is_position_to_find(&self) -> bool {
+		self.position_to_find == Some(self.current_idx)
+	}
+}
+
+impl<'k, 'de, S: ValueSink<'de>> SerializeStruct for ExtractFieldByNameSerializeStructOrMap<'k, 'de, S> {
+	type Ok = ExtractFieldByNameState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		self.serialize_field_if(key == self.key_to_find, value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
+
+impl<'k, 'de, S: ValueSink<'de>> SerializeStructVariant for ExtractFieldByNameSerializeStructOrMap<'k, 'de, S> {
+	type Ok = ExtractFieldByNameState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		self.serialize_field_if(key == self.key_to_find, value)
+	}
+
 	fn end(self) -> Result<Self::Ok, Self::Error> {
 		Ok(self.state)
 	}
 }
 
-impl<'de, S: DeserializeSeed<'de>> SerializeMap for ExtractFieldByNameSerializeStructOrMap<'de, S> {
+impl<'k, 'de, S: ValueSink<'de>> SerializeTupleVariant for ExtractFieldByNameSerializeStructOrMap<'k, 'de, S> {
+	type Ok = ExtractFieldByNameState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		self.serialize_field_if(self.is_position_to_find(), value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
+
+impl<'k, 'de, S: ValueSink<'de>> SerializeTuple for ExtractFieldByNameSerializeStructOrMap<'k, 'de, S> {
+	type Ok = ExtractFieldByNameState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		self.serialize_field_if(self.is_position_to_find(), value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
+
+impl<'k, 'de, S: ValueSink<'de>> SerializeTupleStruct for ExtractFieldByNameSerializeStructOrMap<'k, 'de, S> {
+	type Ok = ExtractFieldByNameState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		self.serialize_field_if(self.is_position_to_find(), value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
+
+impl<'k, 'de, S: ValueSink<'de>> SerializeMap for ExtractFieldByNameSerializeStructOrMap<'k, 'de, S> {
 	type Ok = ExtractFieldByNameState<S, S::Value>;
 	type Error = Error;
 
@@ -229,9 +723,7 @@ impl<'de, S: DeserializeSeed<'de>> SerializeMap for ExtractFieldByNameSerializeS
 		T: Serialize,
 	{
 		if self.current_idx >= self.start_idx {
-			if key.serialize(StringComparisonSerializer {
-				check_if_equals: self.key_to_find,
-			})? {
+			if StringComparisonSerializer::key_is(key, self.key_to_find)? {
 				self.state = match core::mem::replace(&mut self.state, ExtractFieldByNameState::Broken) {
 					ExtractFieldByNameState::NotSeen(seed) | ExtractFieldByNameState::ShouldTakeNext(seed) => {
 						ExtractFieldByNameState::ShouldTakeNext(seed)
@@ -242,7 +734,8 @@ impl<'de, S: DeserializeSeed<'de>> SerializeMap for ExtractFieldByNameSerializeS
 					},
 					more_of_same @ ExtractFieldByNameState::SeenAndMoreOfTheSameAreAvailable { .. } => more_of_same,
 					ExtractFieldByNameState::Broken => {
-						return Err(Error::custom(
+						return Err(Error::new(
+							ErrorCode::Internal,
 							"ExtractFieldByNameState shouldn't be left in Broken \
                         state unless we exited with an error, \
                         in which case we expect this function to not be called again",
@@ -250,7 +743,10 @@ impl<'de, S: DeserializeSeed<'de>> SerializeMap for ExtractFieldByNameSerializeS
 					}
 				}
 			} else if self.start_idx != 0 && self.current_idx == self.start_idx {
-				return Err(Error::custom("Inconsistent serialization is not supported"));
+				return Err(Error::new(
+					ErrorCode::Unsupported,
+					"Inconsistent serialization is not supported",
+				));
 			}
 		}
 		self.current_idx += 1;
@@ -263,10 +759,11 @@ impl<'de, S: DeserializeSeed<'de>> SerializeMap for ExtractFieldByNameSerializeS
 	{
 		self.state = match core::mem::replace(&mut self.state, ExtractFieldByNameState::Broken) {
 			ExtractFieldByNameState::ShouldTakeNext(seed) => {
-				ExtractFieldByNameState::Seen(seed.deserialize(DeserializerFromSerializable { serializable: value })?)
+				ExtractFieldByNameState::Seen(seed.sink(value, self.config)?)
 			}
 			ExtractFieldByNameState::Broken => {
-				return Err(Error::custom(
+				return Err(Error::new(
+					ErrorCode::Internal,
 					"ExtractFieldByNameState shouldn't be left in Broken \
                         state unless we exited with an error, \
                         in which case we expect this function to not be called again",
@@ -282,38 +779,9 @@ impl<'de, S: DeserializeSeed<'de>> SerializeMap for ExtractFieldByNameSerializeS
 		K: Serialize,
 		V: Serialize,
 	{
-		if self.current_idx >= self.start_idx {
-			if key.serialize(StringComparisonSerializer {
-				check_if_equals: self.key_to_find,
-			})? {
-				self.state = match core::mem::replace(&mut self.state, ExtractFieldByNameState::Broken) {
-					ExtractFieldByNameState::NotSeen(seed) => ExtractFieldByNameState::Seen(
-						seed.deserialize(DeserializerFromSerializable { serializable: value })?,
-					),
-					ExtractFieldByNameState::Seen(value) => ExtractFieldByNameState::SeenAndMoreOfTheSameAreAvailable {
-						value,
-						first_next_available: self.current_idx,
-					},
-					more_of_same @ ExtractFieldByNameState::SeenAndMoreOfTheSameAreAvailable { .. } => more_of_same,
-					ExtractFieldByNameState::Broken => {
-						return Err(Error::custom(
-							"ExtractFieldByNameState shouldn't be left in Broken \
-                        state unless we exited with an error, \
-                        in which case we expect this function to not be called again",
-						))
-					}
-					ExtractFieldByNameState::ShouldTakeNext(_) => {
-						return Err(Error::custom(
-							"ExtractFieldByNameState should never enter ShouldTakeNext state",
-						))
-					}
-				};
-			} else if self.start_idx != 0 && self.current_idx == self.start_idx {
-				return Err(Error::custom("Inconsistent serialization is not supported"));
-			}
-		}
-		self.current_idx += 1;
-		Ok(())
+		let is_key_to_find =
+			self.current_idx >= self.start_idx && StringComparisonSerializer::key_is(key, self.key_to_find)?;
+		self.serialize_field_if(is_key_to_find, value)
 	}
 
 	fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -321,18 +789,49 @@ impl<'de, S: DeserializeSeed<'de>> SerializeMap for ExtractFieldByNameSerializeS
 	}
 }
 
-struct StringComparisonSerializer<'a> {
-	check_if_equals: &'a str,
+pub(super) struct StringComparisonSerializer<'a> {
+	pub(super) check_if_equals: &'a str,
 }
+impl StringComparisonSerializer<'_> {
+	/// Whether the map `key` is `name`: keys that aren't strings can't be the one we're looking for
+	pub(super) fn key_is<K: Serialize + ?Sized>(key: &K, name: &str) -> Result<bool, Error> {
+		match key.serialize(StringComparisonSerializer { check_if_equals: name }) {
+			Ok(is_name) => Ok(is_name),
+			Err(e) if e.code() == ErrorCode::Unsupported => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+}
+#[allow(deprecated)] // `serializer_unsupported!` expands to the deprecated `serde_if_integer128!`
 impl Serializer for StringComparisonSerializer<'_> {
 	type Ok = bool;
-	type Error = serde::de::value::Error;
+	type Error = Error;
 	fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
 		Ok(v == self.check_if_equals)
 	}
 
+	fn collect_str<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: core::fmt::Display,
+	{
+		// Compare as the string is being written, which doesn't require allocating it
+		struct Remaining<'a>(Option<&'a str>);
+		impl core::fmt::Write for Remaining<'_> {
+			fn write_str(&mut self, s: &str) -> core::fmt::Result {
+				self.0 = self.0.and_then(|remaining| remaining.strip_prefix(s));
+				match self.0 {
+					Some(_) => Ok(()),
+					None => Err(core::fmt::Error),
+				}
+			}
+		}
+		let mut remaining = Remaining(Some(self.check_if_equals));
+		let _ = core::fmt::write(&mut remaining, format_args!("{}", value));
+		Ok(remaining.0 == Some(""))
+	}
+
 	serializer_unsupported! {
-		err = (<Self::Error as serde::ser::Error>::custom("StringComparisonSerializer only supports comparison through serialize_str"));
+		err = (Error::new(ErrorCode::Unsupported, "StringComparisonSerializer only supports comparison through serialize_str"));
 		bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char bytes none some unit unit_struct
 		unit_variant newtype_struct newtype_variant seq tuple tuple_struct tuple_variant map struct
 		struct_variant i128 u128
@@ -2,7 +2,11 @@ use super::*;
 
 pub struct ThisEnumAccess<'s, S: Serialize + ?Sized> {
 	pub(crate) variant: &'static str,
+	pub(crate) variant_index: u32,
+	/// Known if the enum was hinted with `deserialize_enum`
+	pub(crate) target_variants: Option<&'static [&'static str]>,
 	pub(crate) value: &'s S,
+	pub(crate) config: Config,
 }
 
 impl<'s, 'de, S: Serialize + ?Sized> EnumAccess<'de> for ThisEnumAccess<'s, S> {
@@ -14,22 +18,53 @@ impl<'s, 'de, S: Serialize + ?Sized> EnumAccess<'de> for ThisEnumAccess<'s, S> {
 	where
 		V: DeserializeSeed<'de>,
 	{
+		let by_index = self
+			.config
+			.variant_matching
+			.by_index(self.variant, self.target_variants);
+		let variant = if by_index {
+			seed.deserialize(value::U32Deserializer::new(self.variant_index))?
+		} else {
+			seed.deserialize(value::BorrowedStrDeserializer::new(self.variant))?
+		};
+		// If the target enum doesn't have this variant, the only way the seed accepted it is through a
+		// `#[serde(other)]` catch-all unit variant.
+		// `target_variants` lists aliases along with names, so a variant known through an alias is known, but an
+		// index is only known to be past the target's variants if it's also past its aliases.
+		let unknown_to_target = self.target_variants.is_some_and(|variants| {
+			if by_index {
+				self.variant_index as usize >= variants.len()
+			} else {
+				!variants.contains(&self.variant)
+			}
+		});
 		Ok((
-			seed.deserialize(value::BorrowedStrDeserializer::new(self.variant))?,
-			ThisVariantAccess { value: self.value },
+			variant,
+			ThisVariantAccess {
+				value: self.value,
+				unknown_to_target,
+				config: self.config,
+			},
 		))
 	}
 }
 
 pub struct ThisVariantAccess<'s, S: Serialize + ?Sized> {
 	value: &'s S,
+	unknown_to_target: bool,
+	config: Config,
 }
 
 impl<'de, S: Serialize + ?Sized> VariantAccess<'de> for ThisVariantAccess<'_, S> {
 	type Error = Error;
 
 	fn unit_variant(self) -> Result<(), Self::Error> {
-		Err(Error::invalid_type(Unexpected::NewtypeVariant, &"a unit variant"))
+		if self.unknown_to_target {
+			// Caught by `#[serde(other)]`, which discards the content
+			Ok(())
+		} else {
+			Err(Error::invalid_type(Unexpected::NewtypeVariant, &"a unit variant"))
+		}
 	}
 
 	fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
@@ -38,6 +73,7 @@ impl<'de, S: Serialize + ?Sized> VariantAccess<'de> for ThisVariantAccess<'_, S>
 	{
 		seed.deserialize(DeserializerFromSerializable {
 			serializable: self.value,
+			config: self.config,
 		})
 	}
 
@@ -0,0 +1,751 @@
+//! Paths such as `a.b[3].c`, `items[*].price` or `users[id=42].email`, that look up nested fields and elements
+
+use super::*;
+
+/// One step of a path
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment<'p> {
+	/// `.name`, or `name` at the start of the path
+	Field(&'p str),
+	/// `[3]`
+	Index(usize),
+	/// `[*]` or `.*`: every element of a sequence, every value of a map or struct
+	Wildcard,
+	/// `[id=42]`: the elements (as for [`Segment::Wildcard`]) whose field `id` is equal to `42`
+	///
+	/// Like wildcards, filters may match several values.
+	///
+	/// The literal is compared to numbers, bools, strings, chars and unit variants. Quoting it, e.g. `[name="42"]`,
+	/// only matches strings.
+	Filter { field: &'p str, literal: &'p str },
+}
+
+/// Splits the first segment off a non-empty path
+fn parse_first(path: &str) -> Result<(Segment<'_>, &str), Error> {
+	let (segment, rest) = match path.strip_prefix('[') {
+		Some(inside) => {
+			let end = inside
+				.find(']')
+				.ok_or(Error::new(ErrorCode::InvalidPath, "Unclosed `[` in path"))?;
+			let segment = match &inside[..end] {
+				"*" => Segment::Wildcard,
+				filter => match filter.split_once('=') {
+					Some(("", _)) => return Err(Error::new(ErrorCode::InvalidPath, "Empty field name in path filter")),
+					Some((field, literal)) => Segment::Filter { field, literal },
+					None => Segment::Index(filter.parse().map_err(|_| {
+						Error::new(
+							ErrorCode::InvalidPath,
+							"Expected an index, `*` or `field=value` between `[` and `]` in path",
+						)
+					})?),
+				},
+			};
+			(segment, &inside[end + 1..])
+		}
+		None => {
+			let end = path.find(['.', '[']).unwrap_or(path.len());
+			if end == 0 {
+				return Err(Error::new(ErrorCode::InvalidPath, "Empty field name in path"));
+			}
+			let segment = match &path[..end] {
+				"*" => Segment::Wildcard,
+				name => Segment::Field(name),
+			};
+			(segment, &path[end..])
+		}
+	};
+	match rest.strip_prefix('.') {
+		Some("") => Err(Error::new(ErrorCode::InvalidPath, "Path ends with `.`")),
+		Some(rest) if !rest.starts_with('[') => Ok((segment, rest)),
+		Some(_) => Err(Error::new(ErrorCode::InvalidPath, "Unexpected `.` before `[` in path")),
+		None if rest.is_empty() || rest.starts_with('[') => Ok((segment, rest)),
+		None => Err(Error::new(
+			ErrorCode::InvalidPath,
+			"Expected `.` or `[` after `]` in path",
+		)),
+	}
+}
+
+/// A path to look up in a value, see [`extract_at`](crate::extract_at)
+///
+/// It's either a string such as `a.b[3].c` (a `&str` or `&String`), which is parsed as it's looked up, or segments
+/// built at compile time by [`path!`](crate::path!).
+pub trait Path<'p>: sealed::Sealed<'p> {}
+
+mod sealed {
+	use super::*;
+
+	pub trait Sealed<'p> {
+		type Split: SplitPath<'p>;
+
+		/// The form in which the path is looked up
+		fn split(self) -> Self::Split;
+	}
+
+	pub trait SplitPath<'p>: Copy {
+		/// Splits the first segment off the path, or returns `None` if the path is empty
+		fn split_first(self) -> Result<Option<(Segment<'p>, Self)>, Error>;
+
+		/// Checks the whole path upfront, so that malformed paths are reported even when an earlier segment is absent
+		fn validate(self) -> Result<(), Error>;
+	}
+}
+pub(crate) use sealed::{Sealed, SplitPath};
+
+impl<'p> Path<'p> for &'p str {}
+impl<'p> Sealed<'p> for &'p str {
+	type Split = Self;
+	fn split(self) -> Self {
+		self
+	}
+}
+impl<'p> SplitPath<'p> for &'p str {
+	fn split_first(self) -> Result<Option<(Segment<'p>, Self)>, Error> {
+		match self {
+			"" => Ok(None),
+			path => parse_first(path).map(Some),
+		}
+	}
+
+	fn validate(mut self) -> Result<(), Error> {
+		if self.is_empty() {
+			return Err(Error::new(ErrorCode::InvalidPath, "Empty path"));
+		}
+		while !self.is_empty() {
+			self = parse_first(self)?.1;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'p> Path<'p> for &'p alloc::string::String {}
+#[cfg(feature = "alloc")]
+impl<'p> Sealed<'p> for &'p alloc::string::String {
+	type Split = &'p str;
+	fn split(self) -> &'p str {
+		self
+	}
+}
+
+impl<'p> Path<'p> for &'p [Segment<'p>] {}
+impl<'p> Sealed<'p> for &'p [Segment<'p>] {
+	type Split = Self;
+	fn split(self) -> Self {
+		self
+	}
+}
+impl<'p> SplitPath<'p> for &'p [Segment<'p>] {
+	fn split_first(self) -> Result<Option<(Segment<'p>, Self)>, Error> {
+		Ok(<[_]>::split_first(self).map(|(&segment, rest)| (segment, rest)))
+	}
+
+	fn validate(self) -> Result<(), Error> {
+		match self {
+			[] => Err(Error::new(ErrorCode::InvalidPath, "Empty path")),
+			_ => Ok(()),
+		}
+	}
+}
+
+pub(crate) fn validate<'p>(path: impl SplitPath<'p>) -> Result<(), Error> {
+	path.validate()
+}
+
+/// Whether the (validated) path may match several values, because it has a wildcard or a filter
+pub(crate) fn may_match_several<'p>(mut path: impl SplitPath<'p>) -> Result<bool, Error> {
+	while let Some((segment, rest)) = path.split_first()? {
+		if let Segment::Wildcard | Segment::Filter { .. } = segment {
+			return Ok(true);
+		}
+		path = rest;
+	}
+	Ok(false)
+}
+
+/// Looks up the (validated) `path`, which may only match one value, in the value, and sinks what's there into `sink`
+///
+/// The value is `None` if any segment of the path is absent.
+pub(crate) struct PathSink<P, F> {
+	pub(crate) path: P,
+	pub(crate) sink: F,
+}
+
+impl<'de, P: SplitPath<'de>, F: ValueSink<'de>> ValueSink<'de> for PathSink<P, F> {
+	type Value = Option<F::Value>;
+	fn sink<T: Serialize + ?Sized>(self, value: &T, config: Config) -> Result<Self::Value, Error> {
+		let Some((segment, rest)) = self.path.split_first()? else {
+			return self.sink.sink(value, config).map(Some);
+		};
+		let next = PathSink {
+			path: rest,
+			sink: self.sink,
+		};
+		Ok(lookup(value, segment, next, config)?.flatten())
+	}
+}
+
+/// Sinks the field or element designated by `segment` into `next`, or returns `None` if it's absent
+fn lookup<'de, T: Serialize + ?Sized, K: ValueSink<'de>>(
+	value: &T,
+	segment: Segment<'de>,
+	next: K,
+	config: Config,
+) -> Result<Option<K::Value>, Error> {
+	match segment {
+		Segment::Field(name) => value
+			.serialize(map_access_from_serizable::ExtractFieldByNameSerializer::new(
+				name, next, config,
+			))?
+			.into_option(),
+		Segment::Index(idx) => value
+			.serialize(seq_access_from_serializable::ExtractElementByIndexSerializer::new(
+				idx, next, config,
+			))?
+			.into_option(),
+		Segment::Wildcard | Segment::Filter { .. } => Err(Error::new(
+			ErrorCode::Internal,
+			"Paths that may match several values should be looked up through `Matches`",
+		)),
+	}
+}
+
+/// Receives the values matched by a path that may contain wildcards, one by one
+pub(crate) trait MatchSink {
+	/// Returns whether more matches are wanted
+	fn matched<T: Serialize + ?Sized>(&mut self, value: &T, config: Config) -> Result<bool, Error>;
+}
+
+/// Looks up the (validated) `path`, which may contain wildcards and filters, in the value, and feeds every match into
+/// `sink`
+///
+/// The value is whether more matches are wanted.
+pub(crate) struct Matches<'m, P, M> {
+	pub(crate) path: P,
+	pub(crate) sink: &'m mut M,
+}
+
+impl<'de, P: SplitPath<'de>, M: MatchSink> ValueSink<'de> for Matches<'_, P, M> {
+	type Value = bool;
+	fn sink<T: Serialize + ?Sized>(self, value: &T, config: Config) -> Result<Self::Value, Error> {
+		let Some((segment, rest)) = self.path.split_first()? else {
+			return self.sink.matched(value, config);
+		};
+		let next = Matches {
+			path: rest,
+			sink: self.sink,
+		};
+		match segment {
+			Segment::Wildcard => value.serialize(WildcardSerializer {
+				matches: next,
+				filter: None,
+				config,
+			}),
+			Segment::Filter { field, literal } => value.serialize(WildcardSerializer {
+				matches: next,
+				filter: Some((field, literal)),
+				config,
+			}),
+			segment => Ok(lookup(value, segment, next, config)?.unwrap_or(true)),
+		}
+	}
+}
+
+/// Feeds every element of a sequence, every value of a map or struct, to `matches`
+struct WildcardSerializer<'p, 'm, P, M> {
+	matches: Matches<'m, P, M>,
+	/// Only feed the values whose field (`.0`) is equal to the literal (`.1`)
+	filter: Option<(&'p str, &'p str)>,
+	config: Config,
+}
+
+impl<'p, 'm, P: SplitPath<'p>, M: MatchSink> WildcardSerializer<'p, 'm, P, M> {
+	fn compound(self) -> WildcardCompound<'p, 'm, P, M> {
+		WildcardCompound {
+			path: self.matches.path,
+			sink: self.matches.sink,
+			filter: self.filter,
+			config: self.config,
+			more: true,
+		}
+	}
+}
+
+#[allow(deprecated)] // `serializer_unsupported!` expands to the deprecated `serde_if_integer128!`
+impl<'p, 'm, P: SplitPath<'p>, M: MatchSink> Serializer for WildcardSerializer<'p, 'm, P, M> {
+	type Ok = bool;
+	type Error = Error;
+
+	type SerializeSeq = WildcardCompound<'p, 'm, P, M>;
+	fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeTuple = WildcardCompound<'p, 'm, P, M>;
+	fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeTupleStruct = WildcardCompound<'p, 'm, P, M>;
+	fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeStruct = WildcardCompound<'p, 'm, P, M>;
+	fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
+		Ok(self.compound())
+	}
+
+	type SerializeMap = WildcardCompound<'p, 'm, P, M>;
+	fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		Ok(self.compound())
+	}
+
+	fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+		let mut seq = self.compound();
+		for byte in v {
+			SerializeSeq::serialize_element(&mut seq, byte)?;
+		}
+		SerializeSeq::end(seq)
+	}
+
+	#[cfg(not(feature = "alloc"))]
+	fn collect_str<T: ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: core::fmt::Display,
+	{
+		Err(Error::new(
+			ErrorCode::Unsupported,
+			"Wildcards only apply to sequences, structs and maps",
+		))
+	}
+
+	fn serialize_newtype_struct<T: ?Sized>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(true)
+	}
+
+	serializer_unsupported! {
+		err = (Error::new(ErrorCode::Unsupported, "Wildcards only apply to sequences, structs and maps"));
+		bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str unit unit_struct unit_variant newtype_variant
+		tuple_variant struct_variant i128 u128
+	}
+}
+
+struct WildcardCompound<'p, 'm, P, M> {
+	path: P,
+	sink: &'m mut M,
+	filter: Option<(&'p str, &'p str)>,
+	config: Config,
+	/// Whether more matches are wanted
+	more: bool,
+}
+
+impl<'p, P: SplitPath<'p>, M: MatchSink> WildcardCompound<'p, '_, P, M> {
+	fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+		if self.more && self.passes_filter(value)? {
+			self.more = Matches {
+				path: self.path,
+				sink: &mut *self.sink,
+			}
+			.sink(value, self.config)?;
+		}
+		Ok(())
+	}
+}
+
+impl<M, P> WildcardCompound<'_, '_, P, M> {
+	fn passes_filter<T: Serialize + ?Sized>(&self, value: &T) -> Result<bool, Error> {
+		let Some((field, literal)) = self.filter else {
+			return Ok(true);
+		};
+		match lookup(value, Segment::Field(field), LiteralEquals { literal }, self.config) {
+			Ok(equals) => Ok(equals.unwrap_or(false)),
+			// Values that don't have fields, or fields that can't be compared to a literal, don't pass the filter
+			Err(e) if e.code() == ErrorCode::Unsupported => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+macro_rules! wildcard_compound_impls {
+	($($trait_: ident { $fn_: ident($($arg_ty: ty),*); })*) => {$(
+		impl<'p, P: SplitPath<'p>, M: MatchSink> $trait_ for WildcardCompound<'p, '_, P, M> {
+			type Ok = bool;
+			type Error = Error;
+
+			fn $fn_<T: ?Sized>(&mut self, $(_: $arg_ty,)* value: &T) -> Result<(), Self::Error>
+			where
+				T: Serialize,
+			{
+				self.element(value)
+			}
+
+			fn end(self) -> Result<Self::Ok, Self::Error> {
+				Ok(self.more)
+			}
+		}
+	)*};
+}
+wildcard_compound_impls! {
+	SerializeSeq { serialize_element(); }
+	SerializeTuple { serialize_element(); }
+	SerializeTupleStruct { serialize_field(); }
+	SerializeStruct { serialize_field(&'static str); }
+}
+
+impl<'p, P: SplitPath<'p>, M: MatchSink> SerializeMap for WildcardCompound<'p, '_, P, M> {
+	type Ok = bool;
+	type Error = Error;
+
+	fn serialize_key<T: ?Sized>(&mut self, _: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		Ok(())
+	}
+
+	fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		self.element(value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.more)
+	}
+}
+
+/// Presents the values matched by a path with wildcards or filters as a sequence
+///
+/// The [`Serializer`] is re-driven up to the next match for every element, which is O(n²) where n is the number of
+/// matches.
+pub(crate) struct MatchesDeserializer<'s, P, S: ?Sized> {
+	pub(crate) serializable: &'s S,
+	pub(crate) path: P,
+	pub(crate) config: Config,
+}
+
+impl<'de, P: SplitPath<'de>, S: Serialize + ?Sized> Deserializer<'de> for MatchesDeserializer<'_, P, S> {
+	type Error = Error;
+
+	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_seq(MatchesSeqAccess {
+			serializable: self.serializable,
+			path: self.path,
+			idx: 0,
+			config: self.config,
+		})
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+		unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+	}
+}
+
+struct MatchesSeqAccess<'s, P, S: ?Sized> {
+	serializable: &'s S,
+	path: P,
+	/// Index of the next match
+	idx: usize,
+	config: Config,
+}
+
+impl<'de, P: SplitPath<'de>, S: Serialize + ?Sized> SeqAccess<'de> for MatchesSeqAccess<'_, P, S> {
+	type Error = Error;
+
+	fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+	where
+		T: DeserializeSeed<'de>,
+	{
+		let mut nth = NthMatch {
+			skip: self.idx,
+			seed: Some(seed),
+			value: None,
+		};
+		Matches {
+			path: self.path,
+			sink: &mut nth,
+		}
+		.sink(self.serializable, self.config)?;
+		if nth.value.is_some() {
+			self.idx += 1;
+		}
+		Ok(nth.value)
+	}
+}
+
+/// Deserializes the match after the `skip` first ones
+struct NthMatch<Seed, V> {
+	skip: usize,
+	seed: Option<Seed>,
+	value: Option<V>,
+}
+
+impl<'de, Seed: DeserializeSeed<'de>> MatchSink for NthMatch<Seed, Seed::Value> {
+	fn matched<T: Serialize + ?Sized>(&mut self, value: &T, config: Config) -> Result<bool, Error> {
+		if self.skip > 0 {
+			self.skip -= 1;
+			return Ok(true);
+		}
+		let seed = self
+			.seed
+			.take()
+			.ok_or(Error::new(ErrorCode::Internal, "Match deserialized twice"))?;
+		self.value = Some(seed.deserialize(DeserializerFromSerializable::with_config(value, config))?);
+		Ok(false)
+	}
+}
+
+/// Sinks the first match into `sink`
+pub(crate) struct FirstMatch<F, V> {
+	pub(crate) sink: Option<F>,
+	pub(crate) value: Option<V>,
+}
+
+impl<'de, F: ValueSink<'de>> MatchSink for FirstMatch<F, F::Value> {
+	fn matched<T: Serialize + ?Sized>(&mut self, value: &T, config: Config) -> Result<bool, Error> {
+		let sink = self
+			.sink
+			.take()
+			.ok_or(Error::new(ErrorCode::Internal, "Match sunk twice"))?;
+		self.value = Some(sink.sink(value, config)?);
+		Ok(false)
+	}
+}
+
+/// Whether the value is equal to the literal of a path filter
+struct LiteralEquals<'p> {
+	literal: &'p str,
+}
+
+impl<'de> ValueSink<'de> for LiteralEquals<'_> {
+	type Value = bool;
+	fn sink<T: Serialize + ?Sized>(self, value: &T, _: Config) -> Result<Self::Value, Error> {
+		value.serialize(LiteralComparisonSerializer { literal: self.literal })
+	}
+}
+
+/// Like [`StringComparisonSerializer`](map_access_from_serizable::StringComparisonSerializer), but for numbers, bools,
+/// strings, chars and unit variants
+struct LiteralComparisonSerializer<'p> {
+	literal: &'p str,
+}
+
+impl LiteralComparisonSerializer<'_> {
+	fn parses_to<T: core::str::FromStr + PartialEq>(&self, v: T) -> Result<bool, Error> {
+		Ok(self.literal.parse::<T>().is_ok_and(|literal| literal == v))
+	}
+
+	/// The literal as a string: quotes are optional
+	fn unquoted(&self) -> &str {
+		self.literal
+			.strip_prefix('"')
+			.and_then(|l| l.strip_suffix('"'))
+			.unwrap_or(self.literal)
+	}
+}
+
+macro_rules! compare_parsed {
+	($($fn_: ident($ty: ty),)*) => {$(
+		fn $fn_(self, v: $ty) -> Result<Self::Ok, Self::Error> {
+			self.parses_to(v)
+		}
+	)*};
+}
+
+impl Serializer for LiteralComparisonSerializer<'_> {
+	type Ok = bool;
+	type Error = Error;
+
+	compare_parsed! {
+		serialize_bool(bool),
+		serialize_i8(i8),
+		serialize_i16(i16),
+		serialize_i32(i32),
+		serialize_i64(i64),
+		serialize_i128(i128),
+		serialize_u8(u8),
+		serialize_u16(u16),
+		serialize_u32(u32),
+		serialize_u64(u64),
+		serialize_u128(u128),
+		serialize_f32(f32),
+		serialize_f64(f64),
+	}
+
+	fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+		let mut buf = [0; 4];
+		self.serialize_str(v.encode_utf8(&mut buf))
+	}
+
+	fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+		Ok(self.unquoted() == v)
+	}
+
+	#[cfg(not(feature = "alloc"))]
+	fn collect_str<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: core::fmt::Display,
+	{
+		map_access_from_serizable::StringComparisonSerializer {
+			check_if_equals: self.unquoted(),
+		}
+		.collect_str(value)
+	}
+
+	fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Self::Ok, Self::Error> {
+		self.serialize_str(variant)
+	}
+
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(false)
+	}
+
+	fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_struct<T: ?Sized>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	serializer_unsupported! {
+		err = (Error::new(ErrorCode::Unsupported, "Path filters can only compare numbers, bools, strings, chars and unit variants"));
+		bytes unit unit_struct newtype_variant seq tuple tuple_struct tuple_variant map struct struct_variant
+	}
+}
+
+/// Filter value written in [`path!`](crate::path!), which can't contain escapes since they aren't decoded
+#[doc(hidden)]
+pub const fn __path_filter_literal(literal: &'static str) -> &'static str {
+	let bytes = literal.as_bytes();
+	let mut i = 0;
+	while i < bytes.len() {
+		if bytes[i] == b'\\' {
+			panic!("Filter values of `path!` can't contain escapes");
+		}
+		i += 1;
+	}
+	literal
+}
+
+/// Builds a [`Path`] that is checked at compile time, e.g. `path!(a.b[3].c)`, to use with
+/// [`extract_at`](crate::extract_at)
+///
+/// The syntax is the same as that of string paths: `.field`, `[index]`, `[*]` or `.*`, and `[field=value]`.
+/// The segments are built at compile time, so the path isn't parsed at runtime. It's otherwise looked up the same way:
+/// in particular, the value of a filter is still compared to every element it's tested against.
+///
+/// Fields must be identifiers (raw identifiers aren't supported). Other field names, such as `page-size`, can be used
+/// in string paths, or in segments built by hand, e.g. `&[Segment::Field("page-size")]`. The elements of a tuple are
+/// selected by index like those of a sequence, e.g. `path!(point[0])`.
+///
+/// Filter values are compared as written, so string literals with escapes (e.g. `"a\"b"`) are rejected.
+///
+/// ```
+/// use serde_extract::path;
+///
+/// #[derive(serde_derive::Serialize)]
+/// struct Order {
+/// 	items: Vec<Item>,
+/// }
+/// #[derive(serde_derive::Serialize)]
+/// struct Item {
+/// 	id: u32,
+/// 	price: u32,
+/// }
+/// let order = Order {
+/// 	items: vec![Item { id: 1, price: 3 }, Item { id: 2, price: 5 }],
+/// };
+/// assert_eq!(serde_extract::extract_at::<u32, _>(&order, path!(items[1].price)).unwrap(), 5);
+/// assert_eq!(serde_extract::extract_at::<Vec<u32>, _>(&order, path!(items[id = 1].price)).unwrap(), [3]);
+/// assert_eq!(serde_extract::extract_at::<Vec<u32>, _>(&order, path!(items[*].price)).unwrap(), [3, 5]);
+/// ```
+///
+/// Malformed paths don't compile:
+///
+/// ```compile_fail
+/// let path = serde_extract::path!(items..price);
+/// ```
+///
+/// ```compile_fail
+/// let path = serde_extract::path!(items[x]);
+/// ```
+///
+/// ```compile_fail
+/// let path = serde_extract::path!(items.);
+/// ```
+///
+/// ```compile_fail
+/// let path = serde_extract::path!(items[=1]);
+/// ```
+///
+/// ```compile_fail
+/// let path = serde_extract::path!(items[name = "a\"b"].id);
+/// ```
+#[macro_export]
+macro_rules! path {
+	(@segments [$($segments: expr,)*]) => {{
+		// Built in a constant so that filter values are checked at compile time
+		const PATH: &[$crate::Segment<'static>] = &[$($segments,)*];
+		PATH
+	}};
+	(@segments [$($segments: expr,)*] . * $($rest: tt)*) => {
+		$crate::path!(@segments [$($segments,)* $crate::Segment::Wildcard,] $($rest)*)
+	};
+	(@segments [$($segments: expr,)*] . $field: ident $($rest: tt)*) => {
+		$crate::path!(@segments [$($segments,)* $crate::Segment::Field(stringify!($field)),] $($rest)*)
+	};
+	(@segments [$($segments: expr,)*] [*] $($rest: tt)*) => {
+		$crate::path!(@segments [$($segments,)* $crate::Segment::Wildcard,] $($rest)*)
+	};
+	(@segments [$($segments: expr,)*] [$idx: literal] $($rest: tt)*) => {
+		$crate::path!(@segments [$($segments,)* $crate::Segment::Index($idx),] $($rest)*)
+	};
+	(@segments [$($segments: expr,)*] [$field: ident = $literal: literal] $($rest: tt)*) => {
+		$crate::path!(@segments [$($segments,)* $crate::Segment::Filter {
+			field: stringify!($field),
+			literal: $crate::__path_filter_literal(stringify!($literal)),
+		},] $($rest)*)
+	};
+	(@segments [$($segments: expr,)*] [$field: ident = $literal: ident] $($rest: tt)*) => {
+		$crate::path!(@segments [$($segments,)* $crate::Segment::Filter {
+			field: stringify!($field),
+			literal: stringify!($literal),
+		},] $($rest)*)
+	};
+	(* $($rest: tt)*) => {
+		$crate::path!(@segments [$crate::Segment::Wildcard,] $($rest)*)
+	};
+	($field: ident $($rest: tt)*) => {
+		$crate::path!(@segments [$crate::Segment::Field(stringify!($field)),] $($rest)*)
+	};
+	([$($segment: tt)*] $($rest: tt)*) => {
+		$crate::path!(@segments [] [$($segment)*] $($rest)*)
+	};
+}
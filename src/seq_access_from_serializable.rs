@@ -0,0 +1,283 @@
+use super::*;
+
+/// Feeds the elements of a sequence-like source one by one, re-driving the [`Serializer`] up to the requested element
+/// each time
+///
+/// The fields of a struct (resp. the values of a map) are considered as its elements, in the order they are
+/// serialized.
+pub struct ThisSeqAccess<'s, S: Serialize + ?Sized> {
+	serializable: &'s S,
+	idx: usize,
+	/// Number of elements of the source, as announced when serializing it
+	len: Option<usize>,
+	config: Config,
+}
+
+impl<'s, S: Serialize + ?Sized> ThisSeqAccess<'s, S> {
+	pub(super) fn new(serializable: &'s S, len: Option<usize>, config: Config) -> Self {
+		Self {
+			serializable,
+			idx: 0,
+			len,
+			config,
+		}
+	}
+}
+
+impl<'de, S: Serialize + ?Sized> SeqAccess<'de> for ThisSeqAccess<'_, S> {
+	type Error = Error;
+
+	fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+	where
+		T: DeserializeSeed<'de>,
+	{
+		let element = self
+			.serializable
+			.serialize(ExtractElementByIndexSerializer::new(self.idx, seed, self.config))?
+			.into_option()?;
+		if element.is_some() {
+			self.idx += 1;
+		}
+		Ok(element)
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		self.len.map(|len| len.saturating_sub(self.idx))
+	}
+}
+
+/// Sinks the element at `idx` of the value, if there is one
+pub struct ElementAt<T> {
+	pub(super) idx: usize,
+	pub(super) _spooky: PhantomData<fn() -> T>,
+}
+
+impl<'de, T: DeserializeOwned> ValueSink<'de> for ElementAt<T> {
+	type Value = Option<T>;
+	fn sink<U: Serialize + ?Sized>(self, value: &U, config: Config) -> Result<Self::Value, Error> {
+		value
+			.serialize(ExtractElementByIndexSerializer::new(self.idx, PhantomData::<T>, config))?
+			.into_option()
+	}
+}
+
+pub struct ExtractElementByIndexSerializer<'de, S> {
+	idx_to_find: usize,
+	seed: S,
+	config: Config,
+	_spooky: PhantomData<&'de ()>,
+}
+impl<S> ExtractElementByIndexSerializer<'_, S> {
+	pub(super) fn new(idx_to_find: usize, seed: S, config: Config) -> Self {
+		Self {
+			idx_to_find,
+			seed,
+			config,
+			_spooky: PhantomData,
+		}
+	}
+}
+pub enum ExtractElementByIndexState<Seed, Val> {
+	/// The sequence ended before we could find the element
+	NotSeen(Seed),
+	Seen(Val),
+	Broken,
+}
+impl<Seed, Val> ExtractElementByIndexState<Seed, Val> {
+	/// The element if the sequence has it
+	pub(super) fn into_option(self) -> Result<Option<Val>, Error> {
+		match self {
+			ExtractElementByIndexState::Seen(value) => Ok(Some(value)),
+			ExtractElementByIndexState::NotSeen(_) => Ok(None),
+			ExtractElementByIndexState::Broken => Err(Error::new(
+				ErrorCode::Internal,
+				"Should not happen unless we exited with an error, in which case we shouldn't reach this path",
+			)),
+		}
+	}
+}
+#[allow(deprecated)] // `serializer_unsupported!` expands to the deprecated `serde_if_integer128!`
+impl<'de, S: ValueSink<'de>> Serializer for ExtractElementByIndexSerializer<'de, S> {
+	type Ok = ExtractElementByIndexState<S, S::Value>;
+	type Error = Error;
+
+	type SerializeSeq = ExtractElementByIndexSerializeSeq<'de, S>;
+	fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+		Ok(ExtractElementByIndexSerializeSeq {
+			idx_to_find: self.idx_to_find,
+			state: ExtractElementByIndexState::NotSeen(self.seed),
+			current_idx: 0,
+			config: self.config,
+		})
+	}
+
+	type SerializeTuple = ExtractElementByIndexSerializeSeq<'de, S>;
+	fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		self.serialize_seq(None)
+	}
+
+	type SerializeTupleStruct = ExtractElementByIndexSerializeSeq<'de, S>;
+	fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		self.serialize_seq(None)
+	}
+
+	type SerializeStruct = ExtractElementByIndexSerializeSeq<'de, S>;
+	fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
+		self.serialize_seq(None)
+	}
+
+	type SerializeMap = ExtractElementByIndexSerializeSeq<'de, S>;
+	fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		self.serialize_seq(None)
+	}
+
+	fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+		Ok(match v.get(self.idx_to_find) {
+			Some(&byte) => ExtractElementByIndexState::Seen(self.seed.sink(&byte, self.config)?),
+			None => ExtractElementByIndexState::NotSeen(self.seed),
+		})
+	}
+
+	fn serialize_newtype_struct<T: ?Sized>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(ExtractElementByIndexState::NotSeen(self.seed))
+	}
+
+	serializer_unsupported! {
+		err = (Error::new(ErrorCode::Unsupported, "Can only extract elements from sequences, structs and maps"));
+		bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str unit unit_struct unit_variant newtype_variant
+		tuple_variant struct_variant i128 u128
+	}
+
+	#[cfg(not(feature = "alloc"))]
+	fn collect_str<T: ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: core::fmt::Display,
+	{
+		Err(Error::new(
+			ErrorCode::Unsupported,
+			"Can only extract elements from sequences, structs and maps",
+		))
+	}
+}
+
+pub struct ExtractElementByIndexSerializeSeq<'de, S: ValueSink<'de>> {
+	idx_to_find: usize,
+	state: ExtractElementByIndexState<S, S::Value>,
+	current_idx: usize,
+	config: Config,
+}
+
+impl<'de, S: ValueSink<'de>> SerializeSeq for ExtractElementByIndexSerializeSeq<'de, S> {
+	type Ok = ExtractElementByIndexState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		if self.current_idx == self.idx_to_find {
+			self.state = match core::mem::replace(&mut self.state, ExtractElementByIndexState::Broken) {
+				ExtractElementByIndexState::NotSeen(seed) => {
+					ExtractElementByIndexState::Seen(seed.sink(value, self.config)?)
+				}
+				ExtractElementByIndexState::Seen(_) | ExtractElementByIndexState::Broken => {
+					return Err(Error::new(
+						ErrorCode::Internal,
+						"ExtractElementByIndexState should only ever be NotSeen when reaching the element to find",
+					))
+				}
+			};
+		}
+		self.current_idx += 1;
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
+
+impl<'de, S: ValueSink<'de>> SerializeTuple for ExtractElementByIndexSerializeSeq<'de, S> {
+	type Ok = ExtractElementByIndexState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		SerializeSeq::serialize_element(self, value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
+
+impl<'de, S: ValueSink<'de>> SerializeTupleStruct for ExtractElementByIndexSerializeSeq<'de, S> {
+	type Ok = ExtractElementByIndexState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		SerializeSeq::serialize_element(self, value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
+
+impl<'de, S: ValueSink<'de>> SerializeStruct for ExtractElementByIndexSerializeSeq<'de, S> {
+	type Ok = ExtractElementByIndexState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_field<T: ?Sized>(&mut self, _: &'static str, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		SerializeSeq::serialize_element(self, value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
+
+impl<'de, S: ValueSink<'de>> SerializeMap for ExtractElementByIndexSerializeSeq<'de, S> {
+	type Ok = ExtractElementByIndexState<S, S::Value>;
+	type Error = Error;
+
+	fn serialize_key<T: ?Sized>(&mut self, _: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		Ok(())
+	}
+
+	fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		SerializeSeq::serialize_element(self, value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.state)
+	}
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+/// Extraction as methods on every `Serialize` value
+///
+/// Since these are found before methods reached through `Deref`, they are named so as not to shadow the methods of
+/// common types, e.g. `vec.get(0)` still calls `slice::get` on a `Vec`.
+///
+/// ```
+/// use serde_extract::SerializeExt;
+///
+/// #[derive(serde_derive::Serialize)]
+/// struct Request {
+/// 	field_a: &'static str,
+/// 	page_size: usize,
+/// }
+/// let request = Request {
+/// 	field_a: "hello!",
+/// 	page_size: 2,
+/// };
+/// assert_eq!(request.get_field::<usize>("page_size").unwrap(), Some(2));
+/// assert_eq!(request.extract::<(String, usize)>().unwrap(), ("hello!".to_owned(), 2));
+/// ```
+pub trait SerializeExt: Serialize {
+	/// Extract a `T: DeserializeOwned` from `self`, see [`extract`](crate::extract)
+	fn extract<T: DeserializeOwned>(&self) -> Result<T, Error> {
+		extract(self)
+	}
+
+	/// Extract the field `field` of `self` as a `T: DeserializeOwned`, see [`get`](crate::get)
+	fn get_field<T: DeserializeOwned>(&self, field: &str) -> Result<Option<T>, Error> {
+		get(self, field)
+	}
+
+	/// A `Deserializer` that reads from `self`
+	fn as_deserializer(&self) -> DeserializerFromSerializable<'_, Self> {
+		DeserializerFromSerializable::new(self)
+	}
+}
+
+impl<S: Serialize + ?Sized> SerializeExt for S {}
@@ -0,0 +1,362 @@
+use super::*;
+
+/// Receives the structure of a `Serialize` value, node by node, see [`walk`](crate::walk)
+///
+/// Every [`Walk::enter`] is matched by a [`Walk::exit`] with the same [`Node`] once its contents have been walked.
+/// Within a node, each field (resp. element, map key, map value) is announced by the corresponding method before it
+/// is walked.
+///
+/// All methods do nothing by default, so that implementors only need to override those they care about.
+/// Returning an error stops the walk.
+pub trait Walk {
+	/// A compound value starts
+	fn enter(&mut self, node: Node) -> Result<(), Error> {
+		let _ = node;
+		Ok(())
+	}
+
+	/// The next value is the field `name` of a struct or struct variant
+	fn field(&mut self, name: &'static str) -> Result<(), Error> {
+		let _ = name;
+		Ok(())
+	}
+
+	/// The next value is the element `idx` of a sequence, tuple, tuple struct or tuple variant
+	fn element(&mut self, idx: usize) -> Result<(), Error> {
+		let _ = idx;
+		Ok(())
+	}
+
+	/// The next value is the key of the entry `idx` of a map
+	fn key(&mut self, idx: usize) -> Result<(), Error> {
+		let _ = idx;
+		Ok(())
+	}
+
+	/// The next value is the value of the entry `idx` of a map
+	fn value(&mut self, idx: usize) -> Result<(), Error> {
+		let _ = idx;
+		Ok(())
+	}
+
+	/// A primitive value
+	fn leaf(&mut self, leaf: Leaf<'_>) -> Result<(), Error> {
+		let _ = leaf;
+		Ok(())
+	}
+
+	/// The compound value that was entered last ends
+	fn exit(&mut self, node: Node) -> Result<(), Error> {
+		let _ = node;
+		Ok(())
+	}
+}
+
+/// A value that contains other values, as named in the serde data model
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Node {
+	Struct {
+		name: &'static str,
+		len: usize,
+	},
+	StructVariant {
+		name: &'static str,
+		variant: &'static str,
+		len: usize,
+	},
+	Seq {
+		len: Option<usize>,
+	},
+	Tuple {
+		len: usize,
+	},
+	TupleStruct {
+		name: &'static str,
+		len: usize,
+	},
+	TupleVariant {
+		name: &'static str,
+		variant: &'static str,
+		len: usize,
+	},
+	Map {
+		len: Option<usize>,
+	},
+	Some,
+	NewtypeStruct {
+		name: &'static str,
+	},
+	NewtypeVariant {
+		name: &'static str,
+		variant: &'static str,
+	},
+}
+
+/// A value that doesn't contain other values, as named in the serde data model
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Leaf<'a> {
+	Bool(bool),
+	I8(i8),
+	I16(i16),
+	I32(i32),
+	I64(i64),
+	I128(i128),
+	U8(u8),
+	U16(u16),
+	U32(u32),
+	U64(u64),
+	U128(u128),
+	F32(f32),
+	F64(f64),
+	Char(char),
+	Str(&'a str),
+	Bytes(&'a [u8]),
+	None,
+	Unit,
+	UnitStruct(&'static str),
+	UnitVariant { name: &'static str, variant: &'static str },
+}
+
+/// Drives a [`Walk`] through the value that is serialized with it
+pub(crate) struct WalkSerializer<'w, W: ?Sized> {
+	pub(crate) walker: &'w mut W,
+}
+
+impl<'w, W: Walk + ?Sized> WalkSerializer<'w, W> {
+	fn leaf(self, leaf: Leaf<'_>) -> Result<(), Error> {
+		self.walker.leaf(leaf)
+	}
+
+	fn enter(self, node: Node) -> Result<WalkCompound<'w, W>, Error> {
+		self.walker.enter(node)?;
+		Ok(WalkCompound {
+			walker: self.walker,
+			node,
+			idx: 0,
+		})
+	}
+
+	fn newtype<T: Serialize + ?Sized>(self, node: Node, value: &T) -> Result<(), Error> {
+		self.walker.enter(node)?;
+		value.serialize(WalkSerializer {
+			walker: &mut *self.walker,
+		})?;
+		self.walker.exit(node)
+	}
+}
+
+macro_rules! walk_leaves {
+	($($fn_: ident($ty: ty) => $variant: ident,)*) => {$(
+		fn $fn_(self, v: $ty) -> Result<Self::Ok, Self::Error> {
+			self.leaf(Leaf::$variant(v))
+		}
+	)*};
+}
+
+impl<'w, W: Walk + ?Sized> Serializer for WalkSerializer<'w, W> {
+	type Ok = ();
+	type Error = Error;
+
+	walk_leaves! {
+		serialize_bool(bool) => Bool,
+		serialize_i8(i8) => I8,
+		serialize_i16(i16) => I16,
+		serialize_i32(i32) => I32,
+		serialize_i64(i64) => I64,
+		serialize_i128(i128) => I128,
+		serialize_u8(u8) => U8,
+		serialize_u16(u16) => U16,
+		serialize_u32(u32) => U32,
+		serialize_u64(u64) => U64,
+		serialize_u128(u128) => U128,
+		serialize_f32(f32) => F32,
+		serialize_f64(f64) => F64,
+		serialize_char(char) => Char,
+		serialize_str(&str) => Str,
+		serialize_bytes(&[u8]) => Bytes,
+		serialize_unit_struct(&'static str) => UnitStruct,
+	}
+
+	#[cfg(not(feature = "alloc"))]
+	fn collect_str<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: core::fmt::Display,
+	{
+		general::collect_str_on_stack(value, |v| self.serialize_str(v))
+	}
+
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		self.leaf(Leaf::None)
+	}
+
+	fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+		self.leaf(Leaf::Unit)
+	}
+
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Self::Error> {
+		self.leaf(Leaf::UnitVariant { name, variant })
+	}
+
+	fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		self.newtype(Node::Some, value)
+	}
+
+	fn serialize_newtype_struct<T: ?Sized>(self, name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		self.newtype(Node::NewtypeStruct { name }, value)
+	}
+
+	fn serialize_newtype_variant<T: ?Sized>(
+		self,
+		name: &'static str,
+		_: u32,
+		variant: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Self::Error>
+	where
+		T: Serialize,
+	{
+		self.newtype(Node::NewtypeVariant { name, variant }, value)
+	}
+
+	type SerializeSeq = WalkCompound<'w, W>;
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+		self.enter(Node::Seq { len })
+	}
+
+	type SerializeTuple = WalkCompound<'w, W>;
+	fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+		self.enter(Node::Tuple { len })
+	}
+
+	type SerializeTupleStruct = WalkCompound<'w, W>;
+	fn serialize_tuple_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		self.enter(Node::TupleStruct { name, len })
+	}
+
+	type SerializeTupleVariant = WalkCompound<'w, W>;
+	fn serialize_tuple_variant(
+		self,
+		name: &'static str,
+		_: u32,
+		variant: &'static str,
+		len: usize,
+	) -> Result<Self::SerializeTupleVariant, Self::Error> {
+		self.enter(Node::TupleVariant { name, variant, len })
+	}
+
+	type SerializeMap = WalkCompound<'w, W>;
+	fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		self.enter(Node::Map { len })
+	}
+
+	type SerializeStruct = WalkCompound<'w, W>;
+	fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+		self.enter(Node::Struct { name, len })
+	}
+
+	type SerializeStructVariant = WalkCompound<'w, W>;
+	fn serialize_struct_variant(
+		self,
+		name: &'static str,
+		_: u32,
+		variant: &'static str,
+		len: usize,
+	) -> Result<Self::SerializeStructVariant, Self::Error> {
+		self.enter(Node::StructVariant { name, variant, len })
+	}
+}
+
+pub(crate) struct WalkCompound<'w, W: ?Sized> {
+	walker: &'w mut W,
+	node: Node,
+	/// Index of the next element or entry
+	idx: usize,
+}
+
+impl<W: Walk + ?Sized> WalkCompound<'_, W> {
+	fn walk<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+		value.serialize(WalkSerializer {
+			walker: &mut *self.walker,
+		})
+	}
+
+	fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+		self.walker.element(self.idx)?;
+		self.idx += 1;
+		self.walk(value)
+	}
+
+	fn field<T: Serialize + ?Sized>(&mut self, name: &'static str, value: &T) -> Result<(), Error> {
+		self.walker.field(name)?;
+		self.walk(value)
+	}
+
+	fn end(self) -> Result<(), Error> {
+		self.walker.exit(self.node)
+	}
+}
+
+macro_rules! walk_compound_impls {
+	($($trait_: ident { $fn_: ident($($arg: ident: $arg_ty: ty),*) => $method: ident; })*) => {$(
+		impl<W: Walk + ?Sized> $trait_ for WalkCompound<'_, W> {
+			type Ok = ();
+			type Error = Error;
+
+			fn $fn_<T: ?Sized>(&mut self, $($arg: $arg_ty,)* value: &T) -> Result<(), Self::Error>
+			where
+				T: Serialize,
+			{
+				self.$method($($arg,)* value)
+			}
+
+			fn end(self) -> Result<Self::Ok, Self::Error> {
+				WalkCompound::end(self)
+			}
+		}
+	)*};
+}
+walk_compound_impls! {
+	SerializeSeq { serialize_element() => element; }
+	SerializeTuple { serialize_element() => element; }
+	SerializeTupleStruct { serialize_field() => element; }
+	SerializeTupleVariant { serialize_field() => element; }
+	SerializeStruct { serialize_field(name: &'static str) => field; }
+	SerializeStructVariant { serialize_field(name: &'static str) => field; }
+}
+
+impl<W: Walk + ?Sized> SerializeMap for WalkCompound<'_, W> {
+	type Ok = ();
+	type Error = Error;
+
+	fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		self.walker.key(self.idx)?;
+		self.walk(key)
+	}
+
+	fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize,
+	{
+		self.walker.value(self.idx)?;
+		self.idx += 1;
+		self.walk(value)
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		WalkCompound::end(self)
+	}
+}
@@ -1,4 +1,8 @@
-use serde_extract::extract;
+use serde_extract::{
+	contains_path, describe, elements, elements_at, entries, extract, extract_at, extract_fields, extract_with,
+	find_all_with, first_at, get, get_many, has_field, path, walk, Config, ErrorCode, ExtractFrom, Leaf, Node, Segment,
+	SerializeExt, TupleFields, VariantFields, VariantMatching, Walk,
+};
 
 use serde_derive::*;
 
@@ -166,3 +170,1398 @@ fn option() {
 		}
 	)
 }
+
+#[test]
+fn look_through_wrappers() {
+	#[derive(Serialize)]
+	struct Wrapper(Box<Source>);
+	#[derive(Serialize)]
+	struct Source {
+		a: u32,
+	}
+	#[derive(Serialize)]
+	enum Enum {
+		Variant(Source),
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Extract {
+		a: Option<u32>,
+	}
+	assert_eq!(
+		extract::<Extract, _>(&Wrapper(Box::new(Source { a: 3 }))).unwrap(),
+		Extract { a: Some(3) },
+	);
+	assert_eq!(
+		extract::<Extract, _>(&Some(Source { a: 3 })).unwrap(),
+		Extract { a: Some(3) },
+	);
+	assert_eq!(extract::<Extract, _>(&None::<Source>).unwrap(), Extract { a: None });
+	assert!(extract::<Extract, _>(&Enum::Variant(Source { a: 3 })).is_err());
+	assert_eq!(
+		extract_with::<Extract, _>(
+			&Enum::Variant(Source { a: 3 }),
+			Config::new().look_through_newtype_variants(true)
+		)
+		.unwrap(),
+		Extract { a: Some(3) },
+	);
+}
+
+#[test]
+fn variant_fields() {
+	#[derive(Serialize)]
+	enum Event {
+		Created { id: u64, at: u64 },
+		Deleted(u64, u64),
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Created {
+		id: u64,
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Deleted {
+		#[serde(rename = "0")]
+		id: u64,
+	}
+	let created = Event::Created { id: 1, at: 2 };
+	assert!(extract::<Created, _>(&created).is_err());
+	let any_variant = Config::new().variant_fields(VariantFields::AnyVariant);
+	assert_eq!(
+		extract_with::<Created, _>(&created, any_variant).unwrap(),
+		Created { id: 1 }
+	);
+	assert_eq!(
+		extract_with::<Deleted, _>(&Event::Deleted(3, 4), any_variant).unwrap(),
+		Deleted { id: 3 }
+	);
+	let matching_name = Config::new().variant_fields(VariantFields::MatchingName);
+	assert_eq!(
+		extract_with::<Created, _>(&created, matching_name).unwrap(),
+		Created { id: 1 }
+	);
+	assert!(extract_with::<Deleted, _>(&created, matching_name).is_err());
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct NonCanonical {
+		#[serde(rename = "01")]
+		leading_zero: Option<u64>,
+		#[serde(rename = "+1")]
+		plus: Option<u64>,
+		#[serde(rename = "1")]
+		at: u64,
+	}
+	assert_eq!(
+		extract_with::<NonCanonical, _>(&Event::Deleted(3, 4), any_variant).unwrap(),
+		NonCanonical {
+			leading_zero: None,
+			plus: None,
+			at: 4
+		}
+	);
+}
+
+#[test]
+fn option_of_whole_value() {
+	#[derive(Serialize, Debug, PartialEq, Deserialize)]
+	struct Struct {
+		a: u32,
+	}
+	#[derive(Serialize, Debug, PartialEq, Deserialize)]
+	struct Newtype(u32);
+	#[derive(Serialize, Debug, PartialEq, Deserialize)]
+	enum Enum {
+		A,
+		B(u32),
+	}
+	assert_eq!(
+		extract::<Option<Struct>, _>(&Struct { a: 1 }).unwrap(),
+		Some(Struct { a: 1 })
+	);
+	assert_eq!(extract::<Option<Newtype>, _>(&Newtype(2)).unwrap(), Some(Newtype(2)));
+	assert_eq!(extract::<Option<Enum>, _>(&Enum::A).unwrap(), Some(Enum::A));
+	assert_eq!(extract::<Option<Enum>, _>(&Enum::B(3)).unwrap(), Some(Enum::B(3)));
+	assert_eq!(extract::<Enum, _>(&Enum::A).unwrap(), Enum::A);
+	assert_eq!(extract::<String, _>(&Enum::A).unwrap(), "A");
+	assert_eq!(extract::<Option<Struct>, _>(&None::<Struct>).unwrap(), None);
+	assert_eq!(extract::<Option<u32>, _>(&()).unwrap(), None);
+}
+
+#[test]
+fn missing_fields() {
+	#[derive(Serialize)]
+	struct Source {
+		a: u32,
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Extract {
+		a: u32,
+		#[serde(default)]
+		b: u32,
+		#[serde(default = "three")]
+		c: u32,
+		d: Option<u32>,
+	}
+	fn three() -> u32 {
+		3
+	}
+	assert_eq!(
+		extract::<Extract, _>(&Source { a: 1 }).unwrap(),
+		Extract {
+			a: 1,
+			b: 0,
+			c: 3,
+			d: None,
+		}
+	);
+	#[derive(Debug, Deserialize)]
+	#[allow(dead_code)]
+	struct Missing {
+		x: u32,
+	}
+	assert_eq!(
+		extract::<Missing, _>(&Source { a: 1 }).unwrap_err().to_string(),
+		"missing field `x`"
+	);
+}
+
+#[test]
+fn none_as_missing() {
+	#[derive(Serialize)]
+	struct Source {
+		a: Option<u32>,
+		b: Option<u32>,
+		c: (),
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Extract {
+		#[serde(default)]
+		a: u32,
+		b: u32,
+		#[serde(default)]
+		c: Vec<u32>,
+	}
+	let source = Source {
+		a: None,
+		b: Some(2),
+		c: (),
+	};
+	assert!(extract::<Extract, _>(&source).is_err());
+	assert_eq!(extract::<u32, _>(&Some(2)).unwrap(), 2);
+	assert_eq!(
+		extract_with::<Extract, _>(&source, Config::new().none_as_missing(true)).unwrap(),
+		Extract { a: 0, b: 2, c: vec![] }
+	);
+}
+
+#[test]
+fn nested_option() {
+	#[derive(Serialize)]
+	struct Absent {}
+	#[derive(Serialize)]
+	struct Source<T> {
+		a: T,
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Extract {
+		a: Option<Option<u32>>,
+	}
+	// A present `None` can't be told apart from an absent field unless asked for
+	assert_eq!(
+		extract::<Extract, _>(&Source { a: None::<u32> }).unwrap(),
+		Extract { a: None }
+	);
+	let config = Config::new().present_none_as_some(true);
+	assert_eq!(
+		extract_with::<Extract, _>(&Absent {}, config).unwrap(),
+		Extract { a: None }
+	);
+	assert_eq!(
+		extract_with::<Extract, _>(&Source { a: None::<u32> }, config).unwrap(),
+		Extract { a: Some(None) }
+	);
+	assert_eq!(
+		extract_with::<Extract, _>(&Source { a: Some(None::<u32>) }, config).unwrap(),
+		Extract { a: Some(None) }
+	);
+	assert_eq!(
+		extract_with::<Extract, _>(&Source { a: 3 }, config).unwrap(),
+		Extract { a: Some(Some(3)) }
+	);
+	assert_eq!(
+		extract_with::<Extract, _>(&Source { a: Some(Some(3)) }, config).unwrap(),
+		Extract { a: Some(Some(3)) }
+	);
+	assert_eq!(
+		extract_with::<Option<Option<Option<u32>>>, _>(&None::<u32>, config).unwrap(),
+		Some(None)
+	);
+	// The inner `Option` is the one that has to deserialize from the `None`
+	assert_eq!(extract::<Option<u32>, _>(&None::<u32>).unwrap(), None);
+	extract_with::<Option<u32>, _>(&None::<u32>, config).unwrap_err();
+	let both = config.none_as_missing(true);
+	assert_eq!(
+		extract_with::<Extract, _>(&Source { a: None::<u32> }, both).unwrap(),
+		Extract { a: None }
+	);
+
+	// Aliases and wrappers of the inner `Option`
+	type Patch<T> = Option<Option<T>>;
+	assert_eq!(extract_with::<Patch<u32>, _>(&None::<u32>, config).unwrap(), Some(None));
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Wrapper(Option<u32>);
+	assert_eq!(
+		extract_with::<Option<Wrapper>, _>(&None::<u32>, config).unwrap(),
+		Some(Wrapper(None))
+	);
+	#[derive(Debug, PartialEq, Deserialize)]
+	#[serde(transparent)]
+	struct Transparent(Option<u32>);
+	assert_eq!(
+		extract_with::<Option<Transparent>, _>(&None::<u32>, config).unwrap(),
+		Some(Transparent(None))
+	);
+	#[cfg(feature = "alloc")]
+	assert_eq!(
+		extract_with::<Option<Box<Option<u32>>>, _>(&None::<u32>, config).unwrap(),
+		Some(Box::new(None))
+	);
+
+	// Types that deserialize through `deserialize_option` with their own visitor, even one that expects an "option"
+	#[derive(Debug, PartialEq)]
+	enum Nullable {
+		Null,
+		Value(u32),
+	}
+	impl<'de> serde::Deserialize<'de> for Nullable {
+		fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			struct NullableVisitor;
+			impl<'de> serde::de::Visitor<'de> for NullableVisitor {
+				type Value = Nullable;
+				fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+					f.write_str("option")
+				}
+				fn visit_none<E>(self) -> Result<Nullable, E> {
+					Ok(Nullable::Null)
+				}
+				fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Nullable, D::Error> {
+					serde::Deserialize::deserialize(deserializer).map(Nullable::Value)
+				}
+			}
+			deserializer.deserialize_option(NullableVisitor)
+		}
+	}
+	assert_eq!(extract::<Nullable, _>(&None::<u32>).unwrap(), Nullable::Null);
+	assert_eq!(extract::<Nullable, _>(&Some(3)).unwrap(), Nullable::Value(3));
+	assert_eq!(
+		extract_with::<Option<Nullable>, _>(&None::<u32>, config).unwrap(),
+		Some(Nullable::Null)
+	);
+}
+
+#[test]
+fn variant_matching() {
+	#[derive(Serialize)]
+	enum Source {
+		A,
+		B(u32),
+		C,
+		D(u32),
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	enum Target {
+		A,
+		Second(u32),
+		#[serde(other)]
+		Other,
+	}
+	assert_eq!(extract::<Target, _>(&Source::B(1)).unwrap(), Target::Other);
+	assert_eq!(extract::<Target, _>(&Source::C).unwrap(), Target::Other);
+	let config = Config::new().variant_matching(VariantMatching::IndexIfUnknownName);
+	assert_eq!(extract_with::<Target, _>(&Source::A, config).unwrap(), Target::A);
+	assert_eq!(
+		extract_with::<Target, _>(&Source::B(1), config).unwrap(),
+		Target::Second(1)
+	);
+	assert_eq!(extract_with::<Target, _>(&Source::D(2), config).unwrap(), Target::Other);
+	let config = Config::new().variant_matching(VariantMatching::Index);
+	assert_eq!(extract_with::<Target, _>(&Source::C, config).unwrap(), Target::Other);
+	assert_eq!(
+		extract_with::<Target, _>(&Source::B(1), config).unwrap(),
+		Target::Second(1)
+	);
+}
+
+#[test]
+fn variant_matching_by_index_bounds() {
+	#[derive(Serialize)]
+	enum Source {
+		A,
+		B(u32),
+		C(u32),
+		D(u32),
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	enum Units {
+		X,
+		Y,
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	enum WithOther {
+		X,
+		Y,
+		#[serde(other)]
+		Other,
+	}
+	let config = Config::new().variant_matching(VariantMatching::Index);
+	assert_eq!(extract_with::<Units, _>(&Source::A, config).unwrap(), Units::X);
+	// The content of a newtype variant can't be dropped into a unit variant at the same index
+	extract_with::<Units, _>(&Source::B(1), config).unwrap_err();
+	// Past the target's variants, only `#[serde(other)]` can catch the variant
+	extract_with::<Units, _>(&Source::C(2), config).unwrap_err();
+	assert_eq!(
+		extract_with::<WithOther, _>(&Source::D(3), config).unwrap(),
+		WithOther::Other
+	);
+	// Landing on the `#[serde(other)]` variant by index is a match with a unit variant like any other
+	extract_with::<WithOther, _>(&Source::C(2), config).unwrap_err();
+	extract_with::<WithOther, _>(&Source::B(1), config).unwrap_err();
+}
+
+#[test]
+fn variant_matching_aliases() {
+	#[derive(Serialize)]
+	enum Source {
+		A,
+		Old(u32),
+		Legacy,
+		Gone(u32),
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	enum Target {
+		#[serde(alias = "Legacy")]
+		A,
+		#[serde(alias = "Old")]
+		B(u32),
+		#[serde(other)]
+		Other,
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	enum Units {
+		#[serde(alias = "Old")]
+		X,
+		#[serde(other)]
+		Other,
+	}
+	for variant_matching in [VariantMatching::Name, VariantMatching::IndexIfUnknownName] {
+		let config = Config::new().variant_matching(variant_matching);
+		assert_eq!(extract_with::<Target, _>(&Source::A, config).unwrap(), Target::A);
+		assert_eq!(
+			extract_with::<Target, _>(&Source::Old(1), config).unwrap(),
+			Target::B(1)
+		);
+		assert_eq!(extract_with::<Target, _>(&Source::Legacy, config).unwrap(), Target::A);
+		// An alias is a known name, so the content isn't silently dropped as if caught by `#[serde(other)]`
+		extract_with::<Units, _>(&Source::Old(1), config).unwrap_err();
+	}
+	assert_eq!(extract::<Target, _>(&Source::Gone(1)).unwrap(), Target::Other);
+	// Aliases don't shift indices
+	let config = Config::new().variant_matching(VariantMatching::Index);
+	assert_eq!(
+		extract_with::<Target, _>(&Source::Old(1), config).unwrap(),
+		Target::B(1)
+	);
+	assert_eq!(
+		extract_with::<Target, _>(&Source::Legacy, config).unwrap(),
+		Target::Other
+	);
+}
+
+#[test]
+fn bytes_and_seqs() {
+	struct Bytes<'a>(&'a [u8]);
+	impl serde::Serialize for Bytes<'_> {
+		fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serializer.serialize_bytes(self.0)
+		}
+	}
+	#[derive(Debug, PartialEq)]
+	struct ByteBuf(Vec<u8>);
+	impl<'de> serde::Deserialize<'de> for ByteBuf {
+		fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			struct ByteBufVisitor;
+			impl<'de> serde::de::Visitor<'de> for ByteBufVisitor {
+				type Value = ByteBuf;
+				fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+					formatter.write_str("bytes")
+				}
+				fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+					Ok(ByteBuf(v.to_vec()))
+				}
+				fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+					let mut bytes = Vec::new();
+					while let Some(byte) = seq.next_element()? {
+						bytes.push(byte);
+					}
+					Ok(ByteBuf(bytes))
+				}
+			}
+			deserializer.deserialize_byte_buf(ByteBufVisitor)
+		}
+	}
+	assert_eq!(extract::<Vec<u8>, _>(&Bytes(&[1, 2, 3])).unwrap(), vec![1, 2, 3]);
+	assert_eq!(
+		extract::<ByteBuf, _>(&Bytes(&[1, 2, 3])).unwrap(),
+		ByteBuf(vec![1, 2, 3])
+	);
+	assert_eq!(extract::<ByteBuf, _>(&vec![1u8, 2, 3]).unwrap(), ByteBuf(vec![1, 2, 3]));
+	assert_eq!(extract::<String, _>(&Bytes(b"hello")).unwrap(), "hello");
+	assert!(extract::<String, _>(&Bytes(&[0xff])).is_err());
+	assert_eq!(extract::<Vec<u32>, _>(&[1u32, 2, 3]).unwrap(), vec![1, 2, 3]);
+	assert_eq!(extract::<Vec<u32>, _>(&Some((1u32, 2u32))).unwrap(), vec![1, 2]);
+}
+
+#[test]
+fn char_() {
+	assert_eq!(extract::<char, _>("a").unwrap(), 'a');
+	assert_eq!(extract::<char, _>(&'é').unwrap(), 'é');
+	assert_eq!(extract::<String, _>(&'é').unwrap(), "é");
+	// Formatted messages are only kept with `alloc`
+	#[cfg(feature = "alloc")]
+	assert_eq!(
+		extract::<char, _>("ab").unwrap_err().to_string(),
+		"invalid value: string \"ab\", expected a single character"
+	);
+	assert!(extract::<char, _>("").is_err());
+}
+
+#[test]
+fn tuple_fields() {
+	#[derive(Serialize)]
+	struct Source(i32, i32);
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Point {
+		x: i32,
+		y: i32,
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Second {
+		#[serde(rename = "1")]
+		y: i32,
+	}
+	assert!(extract::<Point, _>(&Source(1, 2)).is_err());
+	let by_position = Config::new().tuple_fields(TupleFields::ByPosition);
+	assert_eq!(
+		extract_with::<Point, _>(&Source(1, 2), by_position).unwrap(),
+		Point { x: 1, y: 2 }
+	);
+	assert_eq!(
+		extract_with::<Point, _>(&(1, 2), by_position).unwrap(),
+		Point { x: 1, y: 2 }
+	);
+	let by_numeric_name = Config::new().tuple_fields(TupleFields::ByNumericName);
+	assert_eq!(
+		extract_with::<Second, _>(&Source(1, 2), by_numeric_name).unwrap(),
+		Second { y: 2 }
+	);
+	assert!(extract_with::<Point, _>(&Source(1, 2), by_numeric_name).is_err());
+}
+
+#[test]
+fn tuples_from_structs() {
+	#[derive(Serialize)]
+	struct Record {
+		id: u32,
+		name: &'static str,
+		score: Option<f64>,
+	}
+	#[derive(Debug, PartialEq, Deserialize)]
+	struct Pair(u32, String);
+	let record = Record {
+		id: 1,
+		name: "a",
+		score: None,
+	};
+	assert_eq!(extract::<(u32, String), _>(&record).unwrap(), (1, "a".to_owned()));
+	assert_eq!(extract::<Pair, _>(&record).unwrap(), Pair(1, "a".to_owned()));
+	assert_eq!(extract::<(u32, u32), _>(&(1u32, 2u32)).unwrap(), (1, 2));
+	assert!(extract::<Vec<u32>, _>(&record).is_err());
+	assert_eq!(
+		extract_fields::<(String, u32), _>(&record, &["name", "id"]).unwrap(),
+		("a".to_owned(), 1)
+	);
+	assert_eq!(
+		extract_fields::<(Option<f64>, Option<u32>), _>(&record, &["score", "missing"]).unwrap(),
+		(None, None)
+	);
+	let error = extract_fields::<(u32, u32), _>(&record, &["id", "missing"]).unwrap_err();
+	assert_eq!(error.code(), ErrorCode::MissingField);
+	#[cfg(feature = "alloc")]
+	assert_eq!(error.to_string(), "missing field `missing`");
+
+	// Field names chosen at runtime
+	let columns = ["name".to_owned(), "id".to_owned()];
+	let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+	assert_eq!(
+		extract_fields::<(String, u32), _>(&record, &columns).unwrap(),
+		("a".to_owned(), 1)
+	);
+}
+
+#[test]
+fn entries_() {
+	#[derive(Serialize)]
+	struct Source {
+		a: u32,
+		b: Option<u32>,
+		c: &'static str,
+	}
+	let source = Source {
+		a: 1,
+		b: Some(2),
+		c: "three",
+	};
+	let mut iter = entries::<String, u32, _>(&source);
+	assert_eq!(iter.next().unwrap().unwrap(), ("a".to_owned(), 1));
+	assert_eq!(iter.next().unwrap().unwrap(), ("b".to_owned(), 2));
+	assert!(iter.next().unwrap().is_err());
+	assert!(iter.next().is_none());
+
+	let map: std::collections::BTreeMap<u8, &str> = [(1, "x"), (2, "y")].into_iter().collect();
+	assert_eq!(
+		entries::<u64, String, _>(&map).collect::<Result<Vec<_>, _>>().unwrap(),
+		vec![(1, "x".to_owned()), (2, "y".to_owned())]
+	);
+	assert_eq!(entries::<String, u32, _>(&None::<Source>).count(), 0);
+	assert!(entries::<String, u32, _>(&[1u32]).next().unwrap().is_err());
+
+	// Entries past the ones that were asked for aren't extracted
+	struct Unreachable;
+	impl serde::Serialize for Unreachable {
+		fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+			panic!("Should not be extracted")
+		}
+	}
+	#[derive(Serialize)]
+	struct Large {
+		a: u32,
+		b: Unreachable,
+	}
+	let large = Large { a: 1, b: Unreachable };
+	let first: Vec<(String, u32)> = entries(&large).take(1).collect::<Result<_, _>>().unwrap();
+	assert_eq!(first, [("a".to_owned(), 1)]);
+}
+
+#[test]
+fn elements_() {
+	#[derive(Serialize)]
+	struct Source {
+		items: Vec<&'static str>,
+		id: u32,
+	}
+	let source = Source {
+		items: vec!["a", "b", "c"],
+		id: 1,
+	};
+	assert_eq!(
+		elements_at::<String, _>(&source, "items")
+			.collect::<Result<Vec<_>, _>>()
+			.unwrap(),
+		["a", "b", "c"]
+	);
+	// Formatted messages are only kept with `alloc`
+	#[cfg(feature = "alloc")]
+	assert_eq!(
+		elements_at::<String, _>(&source, "missing")
+			.next()
+			.unwrap()
+			.unwrap_err()
+			.to_string(),
+		"missing field `missing`"
+	);
+	assert!(elements_at::<String, _>(&source, "id").next().unwrap().is_err());
+	assert_eq!(
+		elements::<u32, _>(&(1u8, 2u16)).collect::<Result<Vec<_>, _>>().unwrap(),
+		[1, 2]
+	);
+	let mut mixed = elements::<u32, _>(&[Some(1u32), None, Some(3)]);
+	assert_eq!(mixed.next().unwrap().unwrap(), 1);
+	assert!(mixed.next().unwrap().is_err());
+	assert!(mixed.next().is_none());
+	assert_eq!(elements::<u32, _>(&None::<Vec<u32>>).count(), 0);
+
+	// Elements past the ones that were asked for aren't extracted
+	struct Unreachable;
+	impl serde::Serialize for Unreachable {
+		fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+			panic!("Should not be extracted")
+		}
+	}
+	let mut lazy = elements::<u32, _>(&(1u32, Unreachable));
+	assert_eq!(lazy.next().unwrap().unwrap(), 1);
+}
+
+#[test]
+fn size_hints() {
+	/// Records the size hints given by the deserializer before each element is extracted, and then at the end
+	#[derive(Debug, PartialEq)]
+	struct SizeHints(Vec<Option<usize>>);
+	struct SizeHintsVisitor;
+	impl<'de> serde::de::Visitor<'de> for SizeHintsVisitor {
+		type Value = SizeHints;
+		fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+			formatter.write_str("a sequence or a map")
+		}
+		fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+			let mut hints = vec![seq.size_hint()];
+			while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {
+				hints.push(seq.size_hint());
+			}
+			Ok(SizeHints(hints))
+		}
+		fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+			let mut hints = vec![map.size_hint()];
+			while map
+				.next_entry::<serde::de::IgnoredAny, serde::de::IgnoredAny>()?
+				.is_some()
+			{
+				hints.push(map.size_hint());
+			}
+			Ok(SizeHints(hints))
+		}
+	}
+	struct InSeq(SizeHints);
+	impl<'de> serde::Deserialize<'de> for InSeq {
+		fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			deserializer.deserialize_seq(SizeHintsVisitor).map(InSeq)
+		}
+	}
+	struct InStruct(SizeHints);
+	impl<'de> serde::Deserialize<'de> for InStruct {
+		fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			deserializer
+				.deserialize_struct("InStruct", &["a", "b", "c"], SizeHintsVisitor)
+				.map(InStruct)
+		}
+	}
+	#[derive(Serialize)]
+	struct Source {
+		a: u32,
+		c: u32,
+	}
+
+	assert_eq!(
+		extract::<InSeq, _>(&[1u32, 2, 3]).unwrap().0,
+		SizeHints(vec![Some(3), Some(2), Some(1), Some(0)])
+	);
+	// The remaining fields are an upper bound, as `b` is only found to be absent after `a` is extracted
+	assert_eq!(
+		extract::<InStruct, _>(&Source { a: 1, c: 2 }).unwrap().0,
+		SizeHints(vec![Some(3), Some(2), Some(0)])
+	);
+	assert_eq!(
+		extract_fields::<InSeq, _>(&Source { a: 1, c: 2 }, &["c", "a"])
+			.unwrap()
+			.0,
+		SizeHints(vec![Some(2), Some(1), Some(0)])
+	);
+}
+
+#[test]
+fn errors() {
+	#[derive(Serialize)]
+	struct Source {
+		a: u32,
+		b: &'static str,
+	}
+	#[derive(Debug, Deserialize)]
+	#[allow(dead_code)]
+	struct Inner {
+		b: u32,
+	}
+	#[derive(Debug, Deserialize)]
+	#[allow(dead_code)]
+	struct Target {
+		a: u32,
+		c: u32,
+	}
+	let source = Source { a: 1, b: "two" };
+
+	let err = extract::<Target, _>(&source).unwrap_err();
+	assert_eq!((err.code(), err.field()), (ErrorCode::MissingField, Some("c")));
+	assert_eq!(err.to_string(), "missing field `c`");
+
+	let err = extract::<Inner, _>(&source).unwrap_err();
+	assert_eq!((err.code(), err.field()), (ErrorCode::InvalidType, Some("b")));
+
+	let err = extract::<Target, _>(&5u32).unwrap_err();
+	assert_eq!((err.code(), err.field()), (ErrorCode::Unsupported, Some("a")));
+	assert_eq!(err.to_string(), "Can only extract from map and struct");
+}
+
+#[test]
+fn collected_strings() {
+	struct Long(usize);
+	impl core::fmt::Display for Long {
+		fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+			(0..self.0).try_for_each(|_| f.write_str("a"))
+		}
+	}
+	impl serde::Serialize for Long {
+		fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serializer.collect_str(self)
+		}
+	}
+	#[derive(Serialize)]
+	struct Item {
+		name: Long,
+	}
+	assert_eq!(extract::<String, _>(&Long(256)).unwrap().len(), 256);
+	let items = [Item { name: Long(300) }];
+	let filter = "[name=".to_owned() + &"a".repeat(300) + "]";
+	assert!(contains_path(&items, filter.as_str()).unwrap());
+	#[cfg(feature = "alloc")]
+	assert_eq!(extract::<String, _>(&Long(300)).unwrap().len(), 300);
+	#[cfg(not(feature = "alloc"))]
+	assert_eq!(
+		extract::<String, _>(&Long(300)).unwrap_err().code(),
+		ErrorCode::Unsupported
+	);
+}
+
+#[test]
+fn presence() {
+	#[derive(Serialize)]
+	struct Source {
+		page_size: Option<usize>,
+		items: Vec<Item>,
+		inner: Option<Item>,
+	}
+	#[derive(Serialize)]
+	struct Item {
+		id: u32,
+	}
+	let source = Source {
+		page_size: None,
+		items: vec![Item { id: 1 }],
+		inner: None,
+	};
+	assert!(has_field(&source, "page_size").unwrap());
+	assert!(!has_field(&source, "cursor").unwrap());
+	assert!(has_field(&5u32, "page_size").is_err());
+	// Keys that aren't strings can't be the field
+	let numbered = std::collections::BTreeMap::from([(1u32, 2u32)]);
+	assert!(!has_field(&numbered, "a").unwrap());
+	assert!(!has_field(&numbered, "1").unwrap());
+
+	assert!(contains_path(&source, "items").unwrap());
+	assert!(contains_path(&source, "items[0].id").unwrap());
+	assert!(!contains_path(&source, "items[1].id").unwrap());
+	assert!(!contains_path(&source, "items[0].name").unwrap());
+	assert!(!contains_path(&source, "inner.id").unwrap());
+	for malformed in [
+		"", "items.", "items[0", "items[a]", "items..id", "items.[0]", ".items", "items[0]id",
+	] {
+		assert_eq!(
+			contains_path(&source, malformed).unwrap_err().code(),
+			ErrorCode::InvalidPath,
+			"{malformed}"
+		);
+	}
+}
+
+#[test]
+fn describe_() {
+	#[derive(Serialize)]
+	struct Source {
+		page_size: Option<u64>,
+		cursor: Option<String>,
+		ids: Vec<u32>,
+		point: (i8, f64),
+		tags: std::collections::BTreeMap<&'static str, Tag>,
+		kind: Kind,
+		empty: Empty,
+		unit: (),
+	}
+	#[derive(Serialize)]
+	struct Tag(char);
+	#[derive(Serialize)]
+	struct Empty {}
+	#[derive(Serialize)]
+	enum Kind {
+		Search { query: String },
+	}
+	let source = Source {
+		page_size: Some(10),
+		cursor: None,
+		ids: vec![1, 2],
+		point: (1, 2.),
+		tags: [("a", Tag('x'))].into_iter().collect(),
+		kind: Kind::Search { query: "q".to_owned() },
+		empty: Empty {},
+		unit: (),
+	};
+	let expected = "Source { page_size: Some(u64), cursor: None, ids: [u32, u32], point: (i8, f64), \
+		tags: {\"a\": Tag(char)}, kind: Kind::Search { query: str }, empty: Empty {}, unit: () }";
+	assert_eq!(describe(&source).to_string(), expected);
+
+	#[cfg(feature = "alloc")]
+	{
+		let shape = describe(&source).shape().unwrap();
+		assert_eq!(shape.to_string(), expected);
+		match shape {
+			serde_extract::Shape::Struct { name: "Source", fields } => {
+				assert_eq!(
+					fields[2],
+					("ids", serde_extract::Shape::Seq(vec![serde_extract::Shape::U32; 2]))
+				);
+			}
+			other => panic!("{other:?}"),
+		}
+	}
+
+	// Values that fail to serialize are described up to the failure
+	struct Failing;
+	impl serde::Serialize for Failing {
+		fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+			Err(serde::ser::Error::custom("nope"))
+		}
+	}
+	assert_eq!(describe(&(1u8, Failing)).to_string(), "(u8, <error>");
+}
+
+#[test]
+fn walk_() {
+	#[derive(Serialize)]
+	struct Source {
+		ids: Vec<u32>,
+		tags: std::collections::BTreeMap<&'static str, bool>,
+		kind: Kind,
+	}
+	#[derive(Serialize)]
+	enum Kind {
+		Search(String),
+	}
+	#[derive(Default)]
+	struct Events(Vec<String>);
+	impl Walk for Events {
+		fn enter(&mut self, node: Node) -> Result<(), serde_extract::Error> {
+			self.0.push(format!("enter {node:?}"));
+			Ok(())
+		}
+		fn field(&mut self, name: &'static str) -> Result<(), serde_extract::Error> {
+			self.0.push(format!("field {name}"));
+			Ok(())
+		}
+		fn element(&mut self, idx: usize) -> Result<(), serde_extract::Error> {
+			self.0.push(format!("element {idx}"));
+			Ok(())
+		}
+		fn key(&mut self, idx: usize) -> Result<(), serde_extract::Error> {
+			self.0.push(format!("key {idx}"));
+			Ok(())
+		}
+		fn value(&mut self, idx: usize) -> Result<(), serde_extract::Error> {
+			self.0.push(format!("value {idx}"));
+			Ok(())
+		}
+		fn leaf(&mut self, leaf: Leaf<'_>) -> Result<(), serde_extract::Error> {
+			self.0.push(format!("leaf {leaf:?}"));
+			Ok(())
+		}
+		fn exit(&mut self, node: Node) -> Result<(), serde_extract::Error> {
+			self.0.push(format!("exit {node:?}"));
+			Ok(())
+		}
+	}
+	let source = Source {
+		ids: vec![7],
+		tags: [("a", true)].into_iter().collect(),
+		kind: Kind::Search("q".to_owned()),
+	};
+	let mut events = Events::default();
+	walk(&source, &mut events).unwrap();
+	assert_eq!(
+		events.0,
+		[
+			"enter Struct { name: \"Source\", len: 3 }",
+			"field ids",
+			"enter Seq { len: Some(1) }",
+			"element 0",
+			"leaf U32(7)",
+			"exit Seq { len: Some(1) }",
+			"field tags",
+			"enter Map { len: Some(1) }",
+			"key 0",
+			"leaf Str(\"a\")",
+			"value 0",
+			"leaf Bool(true)",
+			"exit Map { len: Some(1) }",
+			"field kind",
+			"enter NewtypeVariant { name: \"Kind\", variant: \"Search\" }",
+			"leaf Str(\"q\")",
+			"exit NewtypeVariant { name: \"Kind\", variant: \"Search\" }",
+			"exit Struct { name: \"Source\", len: 3 }",
+		]
+	);
+
+	// Errors returned by the walker stop the walk
+	struct FirstLeaf(usize);
+	impl Walk for FirstLeaf {
+		fn leaf(&mut self, _: Leaf<'_>) -> Result<(), serde_extract::Error> {
+			self.0 += 1;
+			Err(serde::ser::Error::custom("stop"))
+		}
+	}
+	let mut first_leaf = FirstLeaf(0);
+	assert_eq!(walk(&source, &mut first_leaf).unwrap_err().code(), ErrorCode::Custom);
+	assert_eq!(first_leaf.0, 1);
+}
+
+#[test]
+fn find_all_() {
+	#[derive(Serialize)]
+	struct Response {
+		id: u32,
+		items: Vec<Item>,
+		owners: std::collections::BTreeMap<&'static str, Owner>,
+		by_rank: std::collections::BTreeMap<u8, Owner>,
+		event: Event,
+		parent: Option<Item>,
+	}
+	#[derive(Serialize)]
+	struct Item {
+		id: u32,
+	}
+	#[derive(Serialize)]
+	struct Owner {
+		#[serde(rename = "id")]
+		owner_id: u32,
+	}
+	#[derive(Serialize)]
+	enum Event {
+		Created { id: u32 },
+	}
+	let response = Response {
+		id: 1,
+		items: vec![Item { id: 2 }, Item { id: 3 }],
+		owners: [("alice", Owner { owner_id: 4 })].into_iter().collect(),
+		by_rank: [(1, Owner { owner_id: 5 })].into_iter().collect(),
+		event: Event::Created { id: 6 },
+		parent: Some(Item { id: 7 }),
+	};
+
+	let mut found = Vec::new();
+	find_all_with(&response, "id", |id: u32| found.push(id)).unwrap();
+	assert_eq!(found, [1, 2, 3, 4, 5, 6, 7]);
+
+	// Values found under the key are searched as well
+	#[cfg(feature = "alloc")]
+	{
+		#[derive(Serialize, Deserialize, Debug, PartialEq)]
+		struct Nested {
+			id: Option<Box<Nested>>,
+		}
+		let nested = Nested {
+			id: Some(Box::new(Nested { id: None })),
+		};
+		assert_eq!(
+			serde_extract::find_all::<Option<Box<Nested>>, _>(&nested, "id").unwrap(),
+			[Some(Box::new(Nested { id: None })), None]
+		);
+		assert!(serde_extract::find_all::<u32, _>(&response, "cursor")
+			.unwrap()
+			.is_empty());
+	}
+
+	let error = find_all_with(&response, "id", |_: String| {}).unwrap_err();
+	assert_eq!((error.code(), error.field()), (ErrorCode::InvalidType, Some("id")));
+}
+
+#[test]
+fn extract_at_() {
+	#[derive(Serialize)]
+	struct Order {
+		items: Vec<Item>,
+		prices: std::collections::BTreeMap<&'static str, u32>,
+		batches: Vec<Vec<u32>>,
+		note: Option<&'static str>,
+	}
+	#[derive(Serialize)]
+	struct Item {
+		price: u32,
+		name: &'static str,
+	}
+	let order = Order {
+		items: vec![Item { price: 3, name: "a" }, Item { price: 5, name: "b" }],
+		prices: [("a", 3), ("b", 5)].into_iter().collect(),
+		batches: vec![vec![1, 2], vec![], vec![3]],
+		note: None,
+	};
+
+	assert_eq!(extract_at::<u32, _>(&order, "items[1].price").unwrap(), 5);
+	assert_eq!(extract_at::<u32, _>(&order, "prices.b").unwrap(), 5);
+	assert_eq!(extract_at::<Option<u32>, _>(&order, "items[2].price").unwrap(), None);
+	assert_eq!(
+		extract_at::<u32, _>(&order, "items[2].price").unwrap_err().code(),
+		ErrorCode::MissingField
+	);
+
+	// Paths built at runtime
+	#[cfg(feature = "alloc")]
+	{
+		let path = format!("items[{}].price", 1);
+		assert_eq!(extract_at::<u32, _>(&order, &path).unwrap(), 5);
+		assert!(contains_path(&order, &path).unwrap());
+	}
+
+	assert_eq!(extract_at::<Vec<u32>, _>(&order, "items[*].price").unwrap(), [3, 5]);
+	assert_eq!(
+		extract_at::<Vec<String>, _>(&order, "items[*].name").unwrap(),
+		["a", "b"]
+	);
+	assert_eq!(extract_at::<Vec<u32>, _>(&order, "prices.*").unwrap(), [3, 5]);
+	assert_eq!(extract_at::<Vec<u32>, _>(&order, "batches[*][*]").unwrap(), [1, 2, 3]);
+	assert_eq!(extract_at::<Vec<u32>, _>(&order, "batches[1][*]").unwrap(), []);
+	assert_eq!(extract_at::<(u32, u32), _>(&order, "items[*].price").unwrap(), (3, 5));
+	assert_eq!(extract_at::<Vec<u32>, _>(&order, "items[*].cost").unwrap(), []);
+	assert_eq!(extract_at::<Vec<u32>, _>(&order, "note.*").unwrap(), []);
+	assert_eq!(
+		extract_at::<Vec<u32>, _>(&order, "items[*].name").unwrap_err().code(),
+		ErrorCode::InvalidType
+	);
+	assert_eq!(
+		extract_at::<Vec<u32>, _>(&order, "items[0].price.*")
+			.unwrap_err()
+			.code(),
+		ErrorCode::Unsupported
+	);
+	for malformed in ["items[*", "items[*x]", "items.*.", "items.[*]", "items[*]id"] {
+		assert_eq!(
+			extract_at::<Vec<u32>, _>(&order, malformed).unwrap_err().code(),
+			ErrorCode::InvalidPath,
+			"{malformed}"
+		);
+	}
+}
+
+#[test]
+fn path_filters() {
+	#[derive(Serialize)]
+	struct Response {
+		users: Vec<User>,
+		by_name: std::collections::BTreeMap<&'static str, User>,
+	}
+	#[derive(Serialize, Clone)]
+	struct User {
+		id: u64,
+		email: &'static str,
+		admin: bool,
+		score: f32,
+		role: Role,
+		manager: Option<u64>,
+	}
+	#[derive(Serialize, Clone)]
+	enum Role {
+		Owner,
+		Member,
+	}
+	let alice = User {
+		id: 41,
+		email: "alice@example.com",
+		admin: true,
+		score: 1.5,
+		role: Role::Owner,
+		manager: None,
+	};
+	let bob = User {
+		id: 42,
+		email: "bob@example.com",
+		admin: false,
+		score: 2.,
+		role: Role::Member,
+		manager: Some(41),
+	};
+	let response = Response {
+		users: vec![alice.clone(), bob.clone()],
+		by_name: [("alice", alice), ("bob", bob)].into_iter().collect(),
+	};
+
+	let emails = |path| extract_at::<Vec<String>, _>(&response, path).unwrap();
+	assert_eq!(emails("users[id=42].email"), ["bob@example.com"]);
+	assert_eq!(emails("users[email=alice@example.com].email"), ["alice@example.com"]);
+	assert_eq!(emails("users[email=\"bob@example.com\"].email"), ["bob@example.com"]);
+	assert_eq!(emails("users[admin=true].email"), ["alice@example.com"]);
+	assert_eq!(emails("users[score=2].email"), ["bob@example.com"]);
+	assert_eq!(emails("users[role=Member].email"), ["bob@example.com"]);
+	assert_eq!(emails("users[manager=41].email"), ["bob@example.com"]);
+	assert_eq!(emails("by_name[id=41].email"), ["alice@example.com"]);
+	assert_eq!(emails("users[id=43].email"), [""; 0]);
+	assert_eq!(emails("users[id=\"42\"].email"), [""; 0]);
+	assert_eq!(emails("users[name=bob].email"), [""; 0]);
+	assert!(contains_path(&response, "users[id=42]").unwrap());
+	assert!(!contains_path(&response, "users[id=43]").unwrap());
+
+	// A single value is extracted from the first match
+	let email = |path| first_at::<String, _>(&response, path).unwrap();
+	assert_eq!(email("users[id=42].email").as_deref(), Some("bob@example.com"));
+	assert_eq!(email("users[*].email").as_deref(), Some("alice@example.com"));
+	assert_eq!(email("users[id=43].email"), None);
+	assert_eq!(email("users[1].email").as_deref(), Some("bob@example.com"));
+	assert_eq!(email("users[2].email"), None);
+	assert_eq!(
+		extract_at::<String, _>(&response, "users[id=42].email")
+			.unwrap_err()
+			.code(),
+		ErrorCode::InvalidType
+	);
+
+	// Filters match every element that passes them, also when they are combined with wildcards
+	assert_eq!(emails("users[admin=false].email"), ["bob@example.com"]);
+	assert_eq!(
+		extract_at::<Vec<u64>, _>(&response, "users[manager=41].id").unwrap(),
+		[42]
+	);
+	assert_eq!(
+		extract_at::<Vec<Option<u64>>, _>(&response, "users[email=bob@example.com].manager").unwrap(),
+		[Some(41)]
+	);
+	#[derive(Serialize)]
+	struct Line {
+		sku: &'static str,
+		qty: u32,
+	}
+	let lines = vec![
+		Line { sku: "a", qty: 1 },
+		Line { sku: "b", qty: 2 },
+		Line { sku: "a", qty: 3 },
+	];
+	assert_eq!(extract_at::<Vec<u32>, _>(&lines, "[sku=a].qty").unwrap(), [1, 3]);
+	assert_eq!(
+		extract_at::<Vec<u32>, _>(&lines, path!([sku = "a"].qty)).unwrap(),
+		[1, 3]
+	);
+	assert!(contains_path(&lines, "[sku=b]").unwrap());
+	assert!(!contains_path(&lines, "[sku=c].qty").unwrap());
+	assert_eq!(emails("*[id=41].email"), ["alice@example.com", "alice@example.com"]);
+	assert_eq!(emails("users[*].email"), ["alice@example.com", "bob@example.com"]);
+	assert_eq!(
+		extract_at::<u64, _>(&response, "users[id=41].id").unwrap_err().code(),
+		ErrorCode::InvalidType
+	);
+	assert_eq!(
+		extract_at::<Option<u64>, _>(&response, "users[=42].id")
+			.unwrap_err()
+			.code(),
+		ErrorCode::InvalidPath
+	);
+}
+
+#[test]
+fn path_macro() {
+	#[derive(Serialize)]
+	struct Source {
+		users: Vec<User>,
+		#[serde(rename = "type")]
+		kind: &'static str,
+	}
+	#[derive(Serialize)]
+	struct User {
+		id: i64,
+		name: &'static str,
+		admin: bool,
+		tags: Vec<&'static str>,
+	}
+	let source = Source {
+		users: vec![
+			User {
+				id: -1,
+				name: "root",
+				admin: true,
+				tags: vec!["a", "b"],
+			},
+			User {
+				id: 42,
+				name: "bob",
+				admin: false,
+				tags: vec!["c"],
+			},
+		],
+		kind: "users",
+	};
+
+	assert_eq!(
+		path!(users[1].name),
+		&[Segment::Field("users"), Segment::Index(1), Segment::Field("name")]
+	);
+	assert_eq!(path!([0].name), &[Segment::Index(0), Segment::Field("name")]);
+	assert_eq!(
+		path!(users[id = 42].tags.*),
+		&[
+			Segment::Field("users"),
+			Segment::Filter {
+				field: "id",
+				literal: "42"
+			},
+			Segment::Field("tags"),
+			Segment::Wildcard,
+		]
+	);
+
+	let name = |path| extract_at::<Option<String>, _>(&source, path).unwrap();
+	assert_eq!(
+		name(path!(users[1].name)),
+		extract_at::<Option<String>, _>(&source, "users[1].name").unwrap()
+	);
+	assert_eq!(name(path!(users[2].name)), None);
+	assert_eq!(name(path!(type)).as_deref(), Some("users"));
+	let names = |path| extract_at::<Vec<String>, _>(&source, path).unwrap();
+	assert_eq!(names(path!(users[id = 42].name)), ["bob"]);
+	assert_eq!(names(path!(users[id = -1].name)), ["root"]);
+	assert_eq!(names(path!(users[name = "bob"].name)), ["bob"]);
+	assert_eq!(names(path!(users[admin = true].name)), ["root"]);
+	assert_eq!(
+		extract_at::<Vec<String>, _>(&source, path!(users[*].tags[*])).unwrap(),
+		["a", "b", "c"]
+	);
+	assert_eq!(extract_at::<Vec<i64>, _>(&source, path!(users.*[0])).unwrap(), [-1, 42]);
+	assert!(contains_path(&source, path!(users[0].tags[1])).unwrap());
+	assert!(!contains_path(&source, path!(users[1].tags[1])).unwrap());
+}
+
+#[test]
+fn get_() {
+	#[derive(Serialize)]
+	struct Request {
+		field_a: &'static str,
+		page_size: Option<usize>,
+		cursor: Option<u64>,
+		unit: (),
+		tags: std::collections::BTreeMap<&'static str, u8>,
+	}
+	let request = Request {
+		field_a: "hello!",
+		page_size: Some(2),
+		cursor: None,
+		unit: (),
+		tags: [("x", 1)].into_iter().collect(),
+	};
+	assert_eq!(get::<usize, _>(&request, "page_size").unwrap(), Some(2));
+	assert_eq!(
+		get::<String, _>(&request, "field_a").unwrap().as_deref(),
+		Some("hello!")
+	);
+	assert_eq!(get::<u64, _>(&request, "cursor").unwrap(), None);
+	assert_eq!(get::<u64, _>(&request, "unit").unwrap(), None);
+	assert_eq!(get::<u64, _>(&request, "missing").unwrap(), None);
+	assert_eq!(get::<u8, _>(&request.tags, "x").unwrap(), Some(1));
+	assert_eq!(
+		get::<usize, _>(&request, "field_a").unwrap_err().code(),
+		ErrorCode::InvalidType
+	);
+	assert!(get::<usize, _>(&5u32, "page_size").is_err());
+
+	assert_eq!(
+		get_many::<(String, usize, u64, bool), _>(&request, ["field_a", "page_size", "cursor", "missing"]).unwrap(),
+		(Some("hello!".to_owned()), Some(2), None, None)
+	);
+	assert_eq!(get_many::<(usize,), _>(&request, ["page_size"]).unwrap(), (Some(2),));
+	let field = String::from("page_size");
+	assert_eq!(get::<usize, _>(&request, &field).unwrap(), Some(2));
+	assert_eq!(get_many::<(usize,), _>(&request, [&field]).unwrap(), (Some(2),));
+	assert_eq!(
+		get_many::<(usize, usize), _>(&request, ["page_size", "field_a"])
+			.unwrap_err()
+			.code(),
+		ErrorCode::InvalidType
+	);
+}
+
+#[test]
+fn serialize_ext() {
+	#[derive(Serialize)]
+	struct Request {
+		field_a: &'static str,
+		page_size: usize,
+	}
+	let request = Request {
+		field_a: "hello!",
+		page_size: 2,
+	};
+	assert_eq!(request.get_field::<usize>("page_size").unwrap(), Some(2));
+	assert_eq!(request.get_field::<usize>("cursor").unwrap(), None);
+	assert_eq!(request.extract::<(String, usize)>().unwrap(), ("hello!".to_owned(), 2));
+	assert_eq!(3u8.extract::<u64>().unwrap(), 3);
+	assert_eq!(
+		<(String, usize) as serde::Deserialize>::deserialize(request.as_deserializer()).unwrap(),
+		("hello!".to_owned(), 2)
+	);
+
+	// Methods reached through `Deref` aren't shadowed
+	let ids: Vec<u32> = [1, 2].into();
+	assert_eq!(ids.get(1), Some(&2));
+	assert_eq!("hello!".get(..5), Some("hello"));
+
+	// Plugs into serde's value deserializers
+	#[derive(Deserialize, Debug, PartialEq)]
+	struct Target {
+		a: u64,
+		b: Option<u16>,
+	}
+	let (a, b) = (1u32, 2u32);
+	let entries = [("a", a.as_deserializer()), ("b", b.as_deserializer())];
+	let deserializer = serde::de::value::MapDeserializer::<_, serde_extract::Error>::new(entries.into_iter());
+	assert_eq!(
+		<Target as serde::Deserialize>::deserialize(deserializer).unwrap(),
+		Target { a: 1, b: Some(2) }
+	);
+}
+
+#[test]
+fn conversions() {
+	#[derive(Serialize)]
+	struct Dto {
+		id: u32,
+		name: &'static str,
+		tags: Vec<&'static str>,
+	}
+	#[derive(Deserialize, Debug, PartialEq)]
+	struct Domain {
+		id: u32,
+		name: String,
+	}
+	#[derive(Deserialize, Debug, PartialEq)]
+	struct Storage {
+		id: u64,
+		tags: Vec<String>,
+	}
+	#[derive(Deserialize, Debug)]
+	struct Mismatched {
+		#[allow(dead_code)]
+		name: u32,
+	}
+	serde_extract::impl_from_via_extract!(Dto => Domain, Dto => Storage, Dto => Mismatched,);
+
+	let dto = Dto {
+		id: 1,
+		name: "a",
+		tags: vec!["x"],
+	};
+	assert_eq!(
+		Domain::extract_from(&dto).unwrap(),
+		Domain {
+			id: 1,
+			name: "a".to_owned()
+		}
+	);
+	assert_eq!(u8::extract_from(&3u64).unwrap(), 3);
+	assert_eq!(
+		Storage::try_from(&dto).unwrap(),
+		Storage {
+			id: 1,
+			tags: vec!["x".to_owned()]
+		}
+	);
+	let domain: Result<Domain, serde_extract::Error> = (&dto).try_into();
+	assert_eq!(domain.unwrap().id, 1);
+	let error = Mismatched::try_from(&dto).unwrap_err();
+	assert_eq!((error.code(), error.field()), (ErrorCode::InvalidType, Some("name")));
+}